@@ -0,0 +1,217 @@
+//! 自定义别称：口语化地名、内部简称等，构建解析器时并入各级前缀树
+
+use crate::error::ParseError;
+use crate::region::RegionLevel;
+use std::fs;
+use std::path::Path;
+
+/// 一条自定义别称
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alias {
+    /// 别称所指地名的层级
+    pub level: RegionLevel,
+    /// 别称，如"鹏城"
+    pub alias: String,
+    /// 规范名称，如"深圳市"
+    pub name: String,
+    /// 限定的上级地名（城市的上级为省份，区县的上级为城市或省份），为空时全局生效
+    pub parent: Option<String>,
+}
+
+/// 自定义别称表，见 [`AddressParserBuilder::aliases`](crate::AddressParserBuilder::aliases)
+///
+/// 可由代码添加，也可从类 TOML 格式的文本读取（只支持下述写法，并非完整的 TOML）：
+/// 每个层级一节，限定上级时节名写作 `[district."上海市"]`，每行一条 `"别称" = "规范名称"`，
+/// `#` 之后为注释。
+///
+/// ```rust
+/// use cpca::{Aliases, RegionLevel};
+///
+/// let text = r#"
+/// [city]
+/// "鹏城" = "深圳市"
+///
+/// [district."上海市"]
+/// "浦东" = "浦东新区"
+/// "#;
+/// let mut aliases = Aliases::parse(text).unwrap();
+/// aliases.add(RegionLevel::Province, "内蒙", "内蒙古自治区");
+/// assert_eq!(aliases.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aliases {
+    entries: Vec<Alias>,
+}
+
+impl Aliases {
+    /// 创建空的别称表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加全局生效的别称
+    pub fn add(
+        &mut self,
+        level: RegionLevel,
+        alias: impl Into<String>,
+        name: impl Into<String>,
+    ) -> &mut Self {
+        self.push(level, alias.into(), name.into(), None)
+    }
+
+    /// 添加只在指定上级之下生效的别称
+    pub fn add_scoped(
+        &mut self,
+        level: RegionLevel,
+        parent: impl Into<String>,
+        alias: impl Into<String>,
+        name: impl Into<String>,
+    ) -> &mut Self {
+        self.push(level, alias.into(), name.into(), Some(parent.into()))
+    }
+
+    fn push(
+        &mut self,
+        level: RegionLevel,
+        alias: String,
+        name: String,
+        parent: Option<String>,
+    ) -> &mut Self {
+        self.entries.push(Alias {
+            level,
+            alias,
+            name,
+            parent,
+        });
+        self
+    }
+
+    /// 从类 TOML 格式的文本读取，格式有误时返回 [`ParseError::DataLoadError`]
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut aliases = Self::new();
+        let mut section = None;
+        for (number, line) in text.lines().enumerate() {
+            let error = |msg: &str| {
+                ParseError::DataLoadError(format!("line {}: {}: {:?}", number + 1, msg, line))
+            };
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(parse_section(header).ok_or_else(|| error("unknown section"))?);
+                continue;
+            }
+            let (level, parent) = section
+                .clone()
+                .ok_or_else(|| error("alias outside of a section"))?;
+            let (alias, name) = line
+                .split_once('=')
+                .map(|(a, n)| (unquote(a), unquote(n)))
+                .filter(|(a, n)| !a.is_empty() && !n.is_empty())
+                .ok_or_else(|| error("expected \"alias\" = \"name\""))?;
+            aliases.push(level, alias.to_string(), name.to_string(), parent);
+        }
+        Ok(aliases)
+    }
+
+    /// 从文件读取，见 [`parse`](Self::parse)
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| ParseError::DataLoadError(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text)
+    }
+
+    /// 合并另一张别称表
+    pub fn extend(&mut self, other: Aliases) {
+        self.entries.extend(other.entries);
+    }
+
+    /// 全部别称
+    pub fn iter(&self) -> impl Iterator<Item = &Alias> {
+        self.entries.iter()
+    }
+
+    /// 别称数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// 解析节名：`province`、`city`、`district`，可带 `."上级"`
+fn parse_section(header: &str) -> Option<(RegionLevel, Option<String>)> {
+    let (level, parent) = match header.split_once('.') {
+        Some((level, parent)) => (level.trim(), Some(unquote(parent).to_string())),
+        None => (header.trim(), None),
+    };
+    let level = match level {
+        "province" => RegionLevel::Province,
+        "city" => RegionLevel::City,
+        "district" => RegionLevel::District,
+        _ => return None,
+    };
+    // 省份没有上级
+    if level == RegionLevel::Province && parent.is_some() {
+        return None;
+    }
+    Some((level, parent))
+}
+
+/// 去掉引号之外的 `#` 注释
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (pos, ch) in line.char_indices() {
+        match ch {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..pos],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// 去掉首尾空白及一对双引号
+fn unquote(text: &str) -> &str {
+    let text = text.trim();
+    text.strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = r#"
+# 口语化地名
+[province]
+"内蒙" = "内蒙古自治区"
+
+[city]
+魔都 = 上海市   # 不加引号也可以
+
+[district."上海市"]
+"浦东" = "浦东新区"
+"#;
+        let aliases = Aliases::parse(text).unwrap();
+        let entries: Vec<_> = aliases.iter().collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].level, RegionLevel::Province);
+        assert_eq!(entries[1].alias, "魔都");
+        assert_eq!(entries[1].name, "上海市");
+        assert_eq!(entries[2].parent.as_deref(), Some("上海市"));
+
+        assert!(Aliases::parse("\"鹏城\" = \"深圳市\"").is_err());
+        assert!(Aliases::parse("[town]").is_err());
+        assert!(Aliases::parse("[province.\"广东省\"]").is_err());
+        assert!(Aliases::parse("[city]\n鹏城").is_err());
+    }
+}
@@ -0,0 +1,510 @@
+//! 解析器构建器：自定义行政区划数据及匹配行为
+
+use crate::alias::Aliases;
+use crate::data::{
+    load_history, load_regions, parse_regions, parse_towns, parse_villages, with_towns,
+};
+use crate::error::ParseError;
+use crate::fuzzy::FuzzyOptions;
+use crate::parser::AddressParser;
+use crate::preprocess::PreprocessOptions;
+use crate::region::Region;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// 构建前缀树、匹配地名时的选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MatchOptions {
+    /// 是否收录简称（"广东"、"深圳"、"南山"等）
+    pub short_names: bool,
+    /// 简称的最少字数
+    pub min_alias_len: usize,
+    /// 没有省份时，开头既是城市又是区县的地名优先按区县解析
+    pub prefer_district: bool,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            short_names: true,
+            min_alias_len: 2,
+            prefer_district: true,
+        }
+    }
+}
+
+/// 行政区划数据的来源
+enum Source {
+    Builtin,
+    Regions(Vec<Region>),
+    Path(PathBuf),
+    Reader(Box<dyn Read>),
+}
+
+impl Source {
+    /// 读取自定义记录，CSV 文本由 `parse` 解析（`Builtin` 没有自定义记录）
+    fn load(
+        self,
+        parse: fn(&str) -> Result<Vec<Region>, ParseError>,
+    ) -> Result<Vec<Region>, ParseError> {
+        match self {
+            Source::Builtin => Ok(Vec::new()),
+            Source::Regions(regions) => Ok(regions),
+            Source::Path(path) => {
+                let csv = fs::read_to_string(&path)
+                    .map_err(|e| ParseError::DataLoadError(format!("{}: {}", path.display(), e)))?;
+                parse(&csv)
+            }
+            Source::Reader(mut reader) => {
+                let mut csv = String::new();
+                reader
+                    .read_to_string(&mut csv)
+                    .map_err(|e| ParseError::DataLoadError(e.to_string()))?;
+                parse(&csv)
+            }
+        }
+    }
+}
+
+/// 地址解析器构建器，见 [`AddressParser::builder`]
+///
+/// 默认使用内置数据，行为与 [`AddressParser::new`] 相同。自定义数据的格式与内置的
+/// pca.csv 相同（首行为表头，各行为"国家,省,市,区县"），内置的乡镇街道只保留
+/// 所属市、区县在自定义数据中的部分。
+///
+/// 内置的乡镇街道只有不设区的市（东莞、中山、儋州、嘉峪关）的下级单位，
+/// 其他城市的乡镇街道可用 [`towns_from_path`](Self::towns_from_path) 等方法补充。
+///
+/// ```rust
+/// use cpca::{AddressParser, Region};
+///
+/// let parser = AddressParser::builder()
+///     .regions(vec![
+///         Region::new("广东省", "深圳市", Some("南山区".to_string())),
+///         Region::new("广东省", "深圳市", Some("福田区".to_string())),
+///     ])
+///     .min_alias_len(3)
+///     .build()
+///     .unwrap();
+/// assert_eq!(parser.parse("深圳市福田区").district, Some("福田区".to_string()));
+/// assert_eq!(parser.parse("杭州市西湖区").city, None);
+/// ```
+pub struct AddressParserBuilder {
+    source: Source,
+    options: MatchOptions,
+    pinyin: bool,
+    fuzzy: Option<FuzzyOptions>,
+    preprocess: PreprocessOptions,
+    aliases: Aliases,
+    alias_paths: Vec<PathBuf>,
+    towns: Vec<Source>,
+    villages: Vec<Source>,
+}
+
+impl Default for AddressParserBuilder {
+    fn default() -> Self {
+        Self {
+            source: Source::Builtin,
+            options: MatchOptions::default(),
+            pinyin: false,
+            fuzzy: None,
+            preprocess: PreprocessOptions::default(),
+            aliases: Aliases::new(),
+            alias_paths: Vec::new(),
+            towns: Vec::new(),
+            villages: Vec::new(),
+        }
+    }
+}
+
+impl AddressParserBuilder {
+    /// 创建使用内置数据和默认选项的构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用给定的省市区记录代替内置数据
+    pub fn regions(mut self, regions: Vec<Region>) -> Self {
+        self.source = Source::Regions(regions);
+        self
+    }
+
+    /// 从 CSV 文件读取省市区数据
+    pub fn regions_from_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.source = Source::Path(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// 从任意来源读取 CSV 格式的省市区数据
+    pub fn regions_from_reader(mut self, reader: impl Read + 'static) -> Self {
+        self.source = Source::Reader(Box::new(reader));
+        self
+    }
+
+    /// 添加乡镇街道记录（用 [`Region::with_town`] 创建），可多次调用
+    pub fn towns(mut self, towns: Vec<Region>) -> Self {
+        self.towns.push(Source::Regions(towns));
+        self
+    }
+
+    /// 从 CSV 文件读取乡镇街道（首行为表头，各行为"省,市,区县,乡镇"，
+    /// 区县为空表示直属于不设区的市），可多次调用
+    pub fn towns_from_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.towns.push(Source::Path(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// 从任意来源读取 CSV 格式的乡镇街道，格式同 [`towns_from_path`](Self::towns_from_path)
+    pub fn towns_from_reader(mut self, reader: impl Read + 'static) -> Self {
+        self.towns.push(Source::Reader(Box::new(reader)));
+        self
+    }
+
+    /// 添加村、社区记录（`village` 非空的乡镇级记录），可多次调用
+    pub fn villages(mut self, villages: Vec<Region>) -> Self {
+        self.villages.push(Source::Regions(villages));
+        self
+    }
+
+    /// 从 CSV 文件读取村、社区（首行为表头，各行为"省,市,区县,乡镇,村"），可多次调用
+    ///
+    /// 村、社区在所属乡镇已识别时才匹配，也接受"村委会"、"居委会"等写法。
+    pub fn villages_from_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.villages
+            .push(Source::Path(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// 从任意来源读取 CSV 格式的村、社区，格式同 [`villages_from_path`](Self::villages_from_path)
+    pub fn villages_from_reader(mut self, reader: impl Read + 'static) -> Self {
+        self.villages.push(Source::Reader(Box::new(reader)));
+        self
+    }
+
+    /// 是否匹配省、市、区县的简称（默认开启）；关闭后只认全称
+    pub fn short_names(mut self, enabled: bool) -> Self {
+        self.options.short_names = enabled;
+        self
+    }
+
+    /// 简称的最少字数（默认 2），如设为 3 则不再把"南山"识别为南山区
+    pub fn min_alias_len(mut self, chars: usize) -> Self {
+        self.options.min_alias_len = chars;
+        self
+    }
+
+    /// 没有省份时，开头既是城市又是区县的地名是否优先按区县解析（默认开启）
+    ///
+    /// 关闭后只在区县匹配更长时才按区县解析，其余情况按城市解析。
+    pub fn prefer_district(mut self, enabled: bool) -> Self {
+        self.options.prefer_district = enabled;
+        self
+    }
+
+    /// 是否支持拼音输入，见 [`AddressParser::with_pinyin`]
+    pub fn pinyin(mut self, enabled: bool) -> Self {
+        self.pinyin = enabled;
+        self
+    }
+
+    /// 启用地名纠错，见 [`AddressParser::with_fuzzy`]
+    pub fn fuzzy(mut self, options: FuzzyOptions) -> Self {
+        self.fuzzy = Some(options);
+        self
+    }
+
+    /// 输入预处理选项，见 [`AddressParser::with_preprocess`]
+    pub fn preprocess(mut self, options: PreprocessOptions) -> Self {
+        self.preprocess = options;
+        self
+    }
+
+    /// 添加自定义别称（如"魔都"、"鹏城"），可多次调用
+    pub fn aliases(mut self, aliases: Aliases) -> Self {
+        self.aliases.extend(aliases);
+        self
+    }
+
+    /// 从类 TOML 格式的文件读取自定义别称，格式见 [`Aliases`]
+    pub fn aliases_from_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.alias_paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// 构建解析器，数据或别称无法读取、格式有误时返回 [`ParseError::DataLoadError`]
+    ///
+    /// 乡镇街道所属的省、市、区县，村、社区所属的乡镇必须存在。
+    pub fn build(self) -> Result<AddressParser, ParseError> {
+        let mut regions = match self.source {
+            Source::Builtin => with_towns(load_regions()),
+            source => with_subdivisions(validate(source.load(parse_regions)?)?),
+        };
+        for source in self.towns {
+            let towns = source.load(parse_towns)?;
+            validate_children(&regions, &towns, false)?;
+            regions.extend(towns);
+        }
+        for source in self.villages {
+            let villages = source.load(parse_villages)?;
+            validate_children(&regions, &villages, true)?;
+            regions.extend(villages);
+        }
+
+        let mut aliases = self.aliases;
+        for path in &self.alias_paths {
+            aliases.extend(Aliases::from_path(path)?);
+        }
+
+        let mut parser = AddressParser::from_regions(regions, load_history(), self.options);
+        parser.insert_aliases(&aliases)?;
+        parser.set_options(self.pinyin, self.fuzzy, self.preprocess);
+        Ok(parser)
+    }
+}
+
+/// 检查自定义记录：不能为空，省份、城市不能缺失
+fn validate(regions: Vec<Region>) -> Result<Vec<Region>, ParseError> {
+    if regions.is_empty() {
+        return Err(ParseError::DataLoadError("no regions".to_string()));
+    }
+    if let Some(region) = regions
+        .iter()
+        .find(|r| r.province.trim().is_empty() || r.city.trim().is_empty())
+    {
+        return Err(ParseError::DataLoadError(format!(
+            "region without province or city: {:?}",
+            region
+        )));
+    }
+    Ok(regions)
+}
+
+/// 检查自定义的乡镇街道（`villages` 为真时为村、社区）记录：层级正确，且上级已存在
+fn validate_children(
+    regions: &[Region],
+    children: &[Region],
+    villages: bool,
+) -> Result<(), ParseError> {
+    let parents: HashSet<(&str, &str, Option<&str>, Option<&str>)> = regions
+        .iter()
+        .filter(|r| r.village.is_none())
+        .map(|r| {
+            let town = r.town.as_deref();
+            (
+                r.province.as_str(),
+                r.city.as_str(),
+                r.district.as_deref(),
+                town,
+            )
+        })
+        .collect();
+    let orphan = children.iter().find(|r| {
+        let town = r.town.as_deref().filter(|_| villages);
+        r.town.is_none()
+            || r.village.is_some() != villages
+            || !parents.contains(&(
+                r.province.as_str(),
+                r.city.as_str(),
+                r.district.as_deref(),
+                town,
+            ))
+    });
+    match orphan {
+        Some(region) => Err(ParseError::DataLoadError(format!(
+            "{} without a known parent: {:?}",
+            if villages { "village" } else { "town" },
+            region
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// 加上所属市、区县在 `regions` 中的内置乡镇街道
+fn with_subdivisions(mut regions: Vec<Region>) -> Vec<Region> {
+    let parents: HashSet<(String, String, Option<String>)> = regions
+        .iter()
+        .map(|r| (r.province.clone(), r.city.clone(), r.district.clone()))
+        .collect();
+    let known =
+        |r: &Region| parents.contains(&(r.province.clone(), r.city.clone(), r.district.clone()));
+    let subdivisions: Vec<Region> = with_towns(Vec::new()).into_iter().filter(known).collect();
+    regions.extend(subdivisions);
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "country,sheng,shi,qu\n中国,广东省,深圳市,南山区\n中国,广东省,东莞市,\n";
+    const TOWNS: &str = "sheng,shi,qu,zhen\n广东省,深圳市,南山区,粤海街道\n";
+
+    #[test]
+    fn test_custom_regions() {
+        let parser = AddressParserBuilder::new()
+            .regions_from_reader(CSV.as_bytes())
+            .towns_from_reader(TOWNS.as_bytes())
+            .build()
+            .unwrap();
+        let result = parser.parse("深圳南山区粤海街道");
+        assert_eq!(result.district, Some("南山区".to_string()));
+        assert_eq!(result.district_code.as_deref(), Some("440305"));
+        assert_eq!(result.town, Some("粤海街道".to_string()));
+        // 只保留自定义数据下的内置乡镇街道
+        assert_eq!(
+            parser.parse("东莞市长安镇").town,
+            Some("长安镇".to_string())
+        );
+        assert_eq!(parser.parse("中山市小榄镇").town, None);
+        assert_eq!(parser.parse("杭州市西湖区").city, None);
+        assert_eq!(parser.provinces().len(), 1);
+
+        let path = std::env::temp_dir().join(format!("cpca-builder-{}.csv", std::process::id()));
+        fs::write(&path, CSV).unwrap();
+        let parser = AddressParserBuilder::new()
+            .regions_from_path(&path)
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            parser.parse("深圳市南山区").province,
+            Some("广东省".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let error = |builder: AddressParserBuilder| match builder.build() {
+            Err(ParseError::DataLoadError(msg)) => msg,
+            _ => panic!("expected DataLoadError"),
+        };
+        let msg = error(
+            AddressParserBuilder::new().regions_from_reader("表头\n中国,广东省\n".as_bytes()),
+        );
+        assert!(msg.starts_with("line 2"));
+        error(AddressParserBuilder::new().regions_from_path("/nonexistent/pca.csv"));
+        error(AddressParserBuilder::new().regions(Vec::new()));
+        error(AddressParserBuilder::new().regions(vec![Region::new("广东省", "", None)]));
+
+        // 乡镇街道须挂在已有的区县之下
+        let msg = error(
+            AddressParserBuilder::new().towns_from_reader("表头\n广东省,深圳市\n".as_bytes()),
+        );
+        assert!(msg.starts_with("line 2"));
+        error(AddressParserBuilder::new().towns(vec![Region::with_town(
+            "广东省",
+            "深圳市",
+            Some("西湖区".to_string()),
+            "粤海街道",
+        )]));
+        error(AddressParserBuilder::new().towns(vec![Region::new("广东省", "深圳市", None)]));
+        error(
+            AddressParserBuilder::new()
+                .villages_from_reader("表头\n广东省,深圳市,南山区,粤海街道,大冲社区\n".as_bytes()),
+        );
+    }
+
+    #[test]
+    fn test_match_options() {
+        let parser = AddressParserBuilder::new()
+            .short_names(false)
+            .build()
+            .unwrap();
+        assert_eq!(parser.parse("广东深圳").province, None);
+        assert_eq!(
+            parser.parse("广东省深圳市").city,
+            Some("深圳市".to_string())
+        );
+
+        let parser = AddressParserBuilder::new()
+            .min_alias_len(3)
+            .build()
+            .unwrap();
+        assert_eq!(parser.parse("深圳南山").city, None);
+        assert_eq!(
+            parser.parse("黑龙江哈尔滨").province,
+            Some("黑龙江省".to_string())
+        );
+
+        // 同时是城市简称和区县简称时，默认按区县解析
+        let parser = AddressParserBuilder::new().build().unwrap();
+        assert!(parser.parse("朝阳望京").district.is_some());
+        let parser = AddressParserBuilder::new()
+            .prefer_district(false)
+            .build()
+            .unwrap();
+        let result = parser.parse("朝阳望京");
+        assert_eq!(result.city, Some("朝阳市".to_string()));
+        assert_eq!(result.district, None);
+
+        let parser = AddressParserBuilder::new()
+            .pinyin(true)
+            .fuzzy(FuzzyOptions::default())
+            .build()
+            .unwrap();
+        assert_eq!(
+            parser.parse("shenzhen nanshan").district,
+            Some("南山区".to_string())
+        );
+        assert_eq!(
+            parser.parse("深川市南山区").city,
+            Some("深圳市".to_string())
+        );
+        let parser = AddressParserBuilder::new()
+            .preprocess(PreprocessOptions::none())
+            .build()
+            .unwrap();
+        assert_eq!(parser.parse("广东省，深圳市").city, None);
+    }
+
+    #[test]
+    fn test_aliases() {
+        use crate::region::RegionLevel;
+
+        let mut aliases = Aliases::new();
+        aliases
+            .add(RegionLevel::City, "魔都", "上海市")
+            .add(RegionLevel::Province, "内蒙", "内蒙古自治区")
+            .add_scoped(RegionLevel::District, "深圳市", "科技城", "南山区");
+        let path = std::env::temp_dir().join(format!("cpca-aliases-{}.toml", std::process::id()));
+        fs::write(&path, "[city]\n\"鹏城\" = \"深圳市\"\n").unwrap();
+        let parser = AddressParserBuilder::new()
+            .aliases(aliases)
+            .aliases_from_path(&path)
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let result = parser.parse("魔都浦东新区张江");
+        assert_eq!(result.province, Some("上海市".to_string()));
+        assert_eq!(result.district, Some("浦东新区".to_string()));
+        assert_eq!(
+            parser.parse("内蒙呼和浩特").province,
+            Some("内蒙古自治区".to_string())
+        );
+        let result = parser.parse("鹏城科技城高新南一道");
+        assert_eq!(result.city, Some("深圳市".to_string()));
+        assert_eq!(result.district, Some("南山区".to_string()));
+        assert_eq!(result.detail, "高新南一道");
+        // 限定上级的别称只在上级之后生效
+        assert_eq!(parser.parse("科技城").district, None);
+
+        let error = |aliases: Aliases| {
+            matches!(
+                AddressParserBuilder::new().aliases(aliases).build(),
+                Err(ParseError::DataLoadError(_))
+            )
+        };
+        let mut unknown = Aliases::new();
+        unknown.add(RegionLevel::City, "鹏城", "鹏城市");
+        assert!(error(unknown));
+        let mut short = Aliases::new();
+        short.add(RegionLevel::City, "鹏城", "深圳");
+        assert!(error(short));
+        let mut outside = Aliases::new();
+        outside.add_scoped(RegionLevel::District, "广州市", "科技城", "南山区");
+        assert!(error(outside));
+    }
+}
@@ -1,18 +1,93 @@
 //! 省市区数据加载和索引构建
 
-use crate::region::Region;
+use crate::error::ParseError;
+use crate::geo::Coordinate;
+use crate::region::{Region, RegionKind};
+use once_cell::sync::Lazy;
 use std::collections::{HashMap, HashSet};
 
 /// 内嵌的省市区数据（编译时包含）
 const PCA_DATA: &str = include_str!("../data/pca.csv");
 
+/// 内嵌的行政区划代码数据（GB/T 2260）
+const ADCODE_DATA: &str = include_str!("../data/adcode.csv");
+
+/// 内嵌的中心点坐标数据（WGS-84）
+const CENTROID_DATA: &str = include_str!("../data/centroid.csv");
+
+/// 内嵌的邮政编码、电话区号数据
+const POSTCODE_DATA: &str = include_str!("../data/postcode.csv");
+
+/// 内嵌的地名用字拼音表（含多音地名词）
+const PINYIN_DATA: &str = include_str!("../data/pinyin.csv");
+
+/// 内嵌的历史行政区划调整记录
+const HISTORY_DATA: &str = include_str!("../data/history.csv");
+
+/// 内嵌的英文地名（仅收录与拼音拼写不同的官方译名）
+const ENGLISH_DATA: &str = include_str!("../data/english.csv");
+
+/// 内嵌的港澳繁体及常用地名
+const SAR_DATA: &str = include_str!("../data/sar.csv");
+
+/// 内嵌的繁简字对照表
+const FANTI_DATA: &str = include_str!("../data/fanti.csv");
+
 /// 直辖市列表
 pub const MUNICIPALITIES: [&str; 4] = ["北京市", "上海市", "天津市", "重庆市"];
 
-/// 不设区的地级市（直筒子市）
-#[allow(dead_code)]
+/// 不设区的地级市（直筒子市），下一级直接为乡镇街道
 pub const NO_DISTRICT_CITIES: [&str; 4] = ["东莞市", "中山市", "儋州市", "嘉峪关市"];
 
+/// 港澳片区名称，地址中常写在区县之前（如"九龙"、"新界"），本身不是行政区划
+pub const SAR_AREAS: [&str; 9] = [
+    "香港岛",
+    "香港島",
+    "港岛",
+    "港島",
+    "九龙",
+    "九龍",
+    "新界",
+    "澳门半岛",
+    "澳門半島",
+];
+
+/// 判断城市一级的类型
+///
+/// 省直辖县级行政单位的市级代码第 3、4 位为 90（如仙桃市 429004），
+/// 新疆的这类单位均为兵团市；其余按名称判断。未给出代码时先从内置代码表中查找，
+/// 仍查不到代码的普通市县返回 `None`。
+pub fn classify_city(province: &str, city: &str, city_code: Option<&str>) -> Option<RegionKind> {
+    let city_code = city_code.or_else(|| ADCODES.get(&(province, city, "")).copied());
+    if MUNICIPALITIES.contains(&city) {
+        return Some(RegionKind::Municipality);
+    }
+    if city.ends_with("特别行政区") {
+        return Some(RegionKind::SpecialAdministrativeRegion);
+    }
+    if NO_DISTRICT_CITIES.contains(&city) {
+        return Some(RegionKind::NoDistrictCity);
+    }
+    if city_code.is_some_and(|code| code.get(2..4) == Some("90")) {
+        return Some(if province == "新疆维吾尔自治区" {
+            RegionKind::CorpsCity
+        } else {
+            RegionKind::ProvinceAdministered
+        });
+    }
+    if city.ends_with("自治州") {
+        Some(RegionKind::AutonomousPrefecture)
+    } else if city.ends_with("地区") {
+        Some(RegionKind::Prefecture)
+    } else if city.ends_with('盟') {
+        Some(RegionKind::League)
+    } else if city_code.is_some() {
+        Some(RegionKind::PrefectureCity)
+    } else {
+        None
+    }
+}
+
 /// 省份简称映射
 pub fn province_aliases() -> HashMap<&'static str, &'static str> {
     let mut map = HashMap::new();
@@ -90,29 +165,373 @@ pub fn normalize_district_name(district: &str) -> String {
     }
 }
 
+/// 行政区划代码表的键：(省份, 城市, 区县)，省级记录城市与区县为空
+type CodeKey = (&'static str, &'static str, &'static str);
+
+/// 内置行政区划代码表，供按名称补全代码时查找
+static ADCODES: Lazy<HashMap<CodeKey, &'static str>> = Lazy::new(load_adcodes);
+
+/// 加载行政区划代码表
+pub fn load_adcodes() -> HashMap<CodeKey, &'static str> {
+    let mut codes = HashMap::new();
+
+    for line in ADCODE_DATA.lines().skip(1) {
+        // 跳过表头
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() >= 4 && !parts[0].is_empty() {
+            codes.insert((parts[1], parts[2], parts[3]), parts[0]);
+        }
+    }
+
+    codes
+}
+
 /// 加载并解析 CSV 数据
 pub fn load_regions() -> Vec<Region> {
+    // 内置数据的格式由测试保证
+    parse_regions(PCA_DATA).unwrap_or_default()
+}
+
+/// 解析与 pca.csv 格式相同的省市区数据（首行为表头，各行为"国家,省,市,区县"）
+///
+/// 行政区划代码、城市类型按名称从内置数据中查找；缺少省份或城市的行返回 `DataLoadError`。
+pub fn parse_regions(csv: &str) -> Result<Vec<Region>, ParseError> {
+    let lookup = |p: &str, c: &str, d: &str| ADCODES.get(&(p, c, d)).map(|code| code.to_string());
     let mut regions = Vec::new();
 
+    // 跳过表头
+    for (number, line) in csv.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() < 3 || parts[1].is_empty() || parts[2].is_empty() {
+            return Err(ParseError::DataLoadError(format!(
+                "line {}: expected country,province,city[,district], got {:?}",
+                number + 1,
+                line
+            )));
+        }
+        let province = parts[1].to_string();
+        let city = parts[2].to_string();
+        let district = parts
+            .get(3)
+            .filter(|d| !d.is_empty())
+            .map(|d| d.to_string());
+
+        // 不设区的市，其下级单位是乡镇街道，由 load_towns 加载
+        if district.is_some() && NO_DISTRICT_CITIES.contains(&city.as_str()) {
+            continue;
+        }
+
+        let mut region = Region::new(province, city, district);
+        region.province_code = lookup(&region.province, "", "");
+        region.city_code = lookup(&region.province, &region.city, "");
+        region.district_code = region
+            .district
+            .as_deref()
+            .and_then(|d| lookup(&region.province, &region.city, d));
+        region.city_kind =
+            classify_city(&region.province, &region.city, region.city_code.as_deref());
+        regions.push(region);
+    }
+
+    Ok(regions)
+}
+
+/// 在省市区记录之后加上内置的乡镇街道
+pub fn with_towns(mut regions: Vec<Region>) -> Vec<Region> {
+    regions.extend(load_towns());
+    regions
+}
+
+/// 加载内置的乡镇街道数据
+///
+/// 只有不设区的市（东莞、中山、儋州、嘉峪关）在 pca.csv 中的下级单位；
+/// 其他城市的乡镇街道需通过 `AddressParserBuilder::towns_from_path` 等方法提供。
+pub fn load_towns() -> Vec<Region> {
+    let mut towns = Vec::new();
+
     for line in PCA_DATA.lines().skip(1) {
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() > 3 && !parts[3].is_empty() && NO_DISTRICT_CITIES.contains(&parts[2]) {
+            towns.push(Region::with_town(parts[1], parts[2], None, parts[3]));
+        }
+    }
+
+    towns
+}
+
+/// 解析乡镇街道数据（首行为表头，各行为"省,市,区县,乡镇"）
+///
+/// 区县为空表示直属于不设区的市；缺少省份、城市或乡镇的行返回 `DataLoadError`。
+pub fn parse_towns(csv: &str) -> Result<Vec<Region>, ParseError> {
+    let mut towns = Vec::new();
+
+    // 跳过表头
+    for (number, line) in csv.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() < 4 || parts[0].is_empty() || parts[1].is_empty() || parts[3].is_empty() {
+            return Err(ParseError::DataLoadError(format!(
+                "line {}: expected province,city,district,town, got {:?}",
+                number + 1,
+                line
+            )));
+        }
+        let district = Some(parts[2].to_string()).filter(|d| !d.is_empty());
+        towns.push(Region::with_town(parts[0], parts[1], district, parts[3]));
+    }
+
+    Ok(towns)
+}
+
+/// 加载中心点坐标
+///
+/// 键为 (省份, 城市, 区县)，省级、市级记录的下级名称为空字符串。
+pub fn load_centroids() -> HashMap<(String, String, String), Coordinate> {
+    let mut centroids = HashMap::new();
+
+    for line in CENTROID_DATA.lines().skip(1) {
         // 跳过表头
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() >= 3 {
-            let province = parts[1].trim().to_string();
-            let city = parts[2].trim().to_string();
-            let district = if parts.len() > 3 && !parts[3].trim().is_empty() {
-                Some(parts[3].trim().to_string())
-            } else {
-                None
-            };
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() < 5 || parts[0].is_empty() {
+            continue;
+        }
+        if let (Ok(lng), Ok(lat)) = (parts[3].parse(), parts[4].parse()) {
+            centroids.insert(
+                (
+                    parts[0].to_string(),
+                    parts[1].to_string(),
+                    parts[2].to_string(),
+                ),
+                Coordinate::new(lng, lat),
+            );
+        }
+    }
+
+    centroids
+}
+
+/// 邮政编码与电话区号
+#[derive(Debug, Clone, Default)]
+pub struct PostalInfo {
+    /// 邮政编码
+    pub postal_code: Option<String>,
+    /// 电话区号
+    pub area_code: Option<String>,
+}
+
+/// 加载邮政编码、电话区号
+///
+/// 键为 (省份, 城市, 区县)，市级记录的区县为空字符串。
+pub fn load_postal_info() -> HashMap<(String, String, String), PostalInfo> {
+    let mut infos = HashMap::new();
+    let non_empty = |s: &str| Some(s.to_string()).filter(|s| !s.is_empty());
 
-            if !province.is_empty() && !city.is_empty() {
-                regions.push(Region::new(province, city, district));
+    for line in POSTCODE_DATA.lines().skip(1) {
+        // 跳过表头
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() >= 5 && !parts[1].is_empty() {
+            infos.insert(
+                (
+                    parts[0].to_string(),
+                    parts[1].to_string(),
+                    parts[2].to_string(),
+                ),
+                PostalInfo {
+                    postal_code: non_empty(parts[3]),
+                    area_code: non_empty(parts[4]),
+                },
+            );
+        }
+    }
+
+    infos
+}
+
+/// 加载拼音表
+///
+/// 键为单字或多音地名词（如"六安"），值为不带声调的拼音，多个音节以空格分隔。
+pub fn load_pinyin() -> HashMap<&'static str, &'static str> {
+    let mut table = HashMap::new();
+
+    for line in PINYIN_DATA.lines().skip(1) {
+        // 跳过表头
+        if let Some((hanzi, pinyin)) = line.split_once(',') {
+            let (hanzi, pinyin) = (hanzi.trim(), pinyin.trim());
+            if !hanzi.is_empty() && !pinyin.is_empty() {
+                table.insert(hanzi, pinyin);
             }
         }
     }
 
-    regions
+    table
+}
+
+/// 历史行政区划调整记录：已撤销或更名的地区及其现行承继地区
+#[derive(Debug, Clone)]
+pub struct Redirect {
+    /// 省份
+    pub province: String,
+    /// 原城市
+    pub city: String,
+    /// 原区县（为空表示整个城市撤销或更名）
+    pub district: Option<String>,
+    /// 现城市
+    pub new_city: String,
+    /// 现区县
+    pub new_district: Option<String>,
+    /// 生效日期
+    ///
+    /// 有确切批复日期时为 `YYYY-MM-DD`；由 GB/T 2260 历年代码表比对得出的调整
+    /// 为 `YYYY-MM`（首次体现该调整的代码表月份），2016 年之后的代码表缺失年份
+    /// 的调整为 `YYYY`（首次体现该调整的代码表年份），均表示不晚于该时间。
+    pub effective: String,
+    /// 是否并入已有地区（否则为更名或改隶，承继地区由原地区改名而来）
+    #[cfg_attr(not(feature = "vintages"), allow(dead_code))]
+    pub merge: bool,
+}
+
+impl Redirect {
+    /// 原地名（城市与区县相连，如"襄樊市襄阳区"）
+    pub fn old_name(&self) -> String {
+        format!(
+            "{}{}",
+            self.city,
+            self.district.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// 现行地名
+    pub fn new_name(&self) -> String {
+        format!(
+            "{}{}",
+            self.new_city,
+            self.new_district.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// 生效年份
+    #[cfg_attr(not(feature = "vintages"), allow(dead_code))]
+    pub fn effective_year(&self) -> u16 {
+        self.effective
+            .get(..4)
+            .and_then(|y| y.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// 加载历史行政区划调整记录
+pub fn load_history() -> Vec<Redirect> {
+    let mut redirects = Vec::new();
+    let non_empty = |s: &str| Some(s.to_string()).filter(|s| !s.is_empty());
+
+    for line in HISTORY_DATA.lines().skip(1) {
+        // 跳过表头
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() >= 6 && !parts[0].is_empty() && !parts[1].is_empty() {
+            redirects.push(Redirect {
+                province: parts[0].to_string(),
+                city: parts[1].to_string(),
+                district: non_empty(parts[2]),
+                new_city: parts[3].to_string(),
+                new_district: non_empty(parts[4]),
+                effective: parts[5].to_string(),
+                merge: parts.get(6) == Some(&"merge"),
+            });
+        }
+    }
+
+    redirects
+}
+
+/// 加载英文地名表，返回 (中文全称, 英文名)
+pub fn load_english_names() -> Vec<(&'static str, &'static str)> {
+    ENGLISH_DATA
+        .lines()
+        .skip(1) // 跳过表头
+        .filter_map(|line| line.split_once(','))
+        .map(|(name, english)| (name.trim(), english.trim()))
+        .filter(|(name, english)| !name.is_empty() && !english.is_empty())
+        .collect()
+}
+
+/// 加载港澳地名的繁体写法及常用别称
+///
+/// 返回 (特别行政区, 区县, 别称)，区县为空时别称指特别行政区本身。
+pub fn load_sar_aliases() -> Vec<(&'static str, Option<&'static str>, &'static str)> {
+    let mut aliases = Vec::new();
+
+    for line in SAR_DATA.lines().skip(1) {
+        // 跳过表头
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() >= 4 && !parts[0].is_empty() {
+            let district = Some(parts[1]).filter(|d| !d.is_empty());
+            for alias in parts[2..].iter().flat_map(|p| p.split('|')) {
+                if !alias.is_empty() {
+                    aliases.push((parts[0], district, alias));
+                }
+            }
+        }
+    }
+
+    aliases
+}
+
+/// 加载繁简字对照表
+///
+/// 返回 (繁体字, 简体字)；同一简体字对应多个繁体字时，排在前面的为输出繁体时的首选。
+pub fn load_fanti() -> Vec<(char, char)> {
+    FANTI_DATA
+        .lines()
+        .skip(1) // 跳过表头
+        .filter_map(|line| line.split_once(','))
+        .filter_map(|(fanti, jianti)| {
+            let mut chars = fanti.trim().chars().chain(jianti.trim().chars());
+            match (chars.next(), chars.next(), chars.next()) {
+                (Some(fanti), Some(jianti), None) => Some((fanti, jianti)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// 解析村、社区数据（首行为表头，各行为"省,市,区县,乡镇,村"）
+///
+/// 区县为空表示乡镇直属于不设区的市；缺少其他各级的行返回 `DataLoadError`。
+pub fn parse_villages(csv: &str) -> Result<Vec<Region>, ParseError> {
+    let mut villages = Vec::new();
+
+    // 跳过表头
+    for (number, line) in csv.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+        if parts.len() < 5
+            || parts
+                .iter()
+                .take(5)
+                .enumerate()
+                .any(|(i, p)| i != 2 && p.is_empty())
+        {
+            return Err(ParseError::DataLoadError(format!(
+                "line {}: expected province,city,district,town,village, got {:?}",
+                number + 1,
+                line
+            )));
+        }
+        let district = Some(parts[2].to_string()).filter(|d| !d.is_empty());
+        villages.push(Region {
+            village: Some(parts[4].to_string()),
+            ..Region::with_town(parts[0], parts[1], district, parts[3])
+        });
+    }
+
+    Ok(villages)
 }
 
 /// 构建各种索引
@@ -131,6 +550,26 @@ pub struct RegionIndex {
     pub cities: HashSet<String>,
     /// 所有区县集合
     pub districts: HashSet<String>,
+    /// 所有乡镇街道集合
+    pub towns: HashSet<String>,
+    /// 乡镇街道 -> 所属记录（可能有多个同名乡镇）
+    pub town_to_regions: HashMap<String, Vec<Region>>,
+    /// 所有村、社区集合
+    pub villages: HashSet<String>,
+    /// 村、社区 -> 所属记录
+    pub village_to_regions: HashMap<String, Vec<Region>>,
+    /// 省份 -> 行政区划代码
+    pub province_codes: HashMap<String, String>,
+    /// 城市 -> 行政区划代码
+    pub city_codes: HashMap<String, String>,
+    /// 城市 -> 城市类型
+    pub city_kinds: HashMap<String, RegionKind>,
+    /// (城市, 区县) -> 行政区划代码
+    pub district_codes: HashMap<(String, String), String>,
+    /// 行政区划代码 -> 地区记录（市级、区县级）
+    pub code_to_region: HashMap<String, Region>,
+    /// (城市, 区县) -> 地区记录，市级记录的区县为空字符串
+    pub city_district_regions: HashMap<(String, String), Region>,
 }
 
 impl RegionIndex {
@@ -143,8 +582,67 @@ impl RegionIndex {
         let mut district_to_city: HashMap<String, Vec<(String, String)>> = HashMap::new();
         let mut cities = HashSet::new();
         let mut districts = HashSet::new();
+        let mut towns = HashSet::new();
+        let mut town_to_regions: HashMap<String, Vec<Region>> = HashMap::new();
+        let mut villages = HashSet::new();
+        let mut village_to_regions: HashMap<String, Vec<Region>> = HashMap::new();
+        let mut province_codes = HashMap::new();
+        let mut city_codes = HashMap::new();
+        let mut city_kinds = HashMap::new();
+        let mut district_codes = HashMap::new();
+        let mut code_to_region = HashMap::new();
+        let mut city_district_regions = HashMap::new();
 
         for region in regions {
+            // 村、社区
+            if let Some(ref village) = region.village {
+                villages.insert(village.clone());
+                village_to_regions
+                    .entry(village.clone())
+                    .or_default()
+                    .push(region.clone());
+                continue;
+            }
+
+            // 乡镇街道
+            if let Some(ref town) = region.town {
+                towns.insert(town.clone());
+                town_to_regions
+                    .entry(town.clone())
+                    .or_default()
+                    .push(region.clone());
+                continue;
+            }
+
+            city_district_regions.insert(
+                (
+                    region.city.clone(),
+                    region.district.clone().unwrap_or_default(),
+                ),
+                region.clone(),
+            );
+
+            // 行政区划代码
+            if let Some(ref code) = region.province_code {
+                province_codes.insert(region.province.clone(), code.clone());
+            }
+            if let Some(ref code) = region.city_code {
+                city_codes.insert(region.city.clone(), code.clone());
+            }
+            // 自行构造的记录可能未设置类型，按代码和名称补上
+            if let Some(kind) = region.city_kind.or_else(|| {
+                classify_city(&region.province, &region.city, region.city_code.as_deref())
+            }) {
+                city_kinds.insert(region.city.clone(), kind);
+            }
+            if let Some(code) = region.code() {
+                code_to_region.insert(code.to_string(), region.clone());
+                if let Some(ref district) = region.district {
+                    district_codes
+                        .insert((region.city.clone(), district.clone()), code.to_string());
+                }
+            }
+
             // 省份
             provinces.insert(region.province.clone());
 
@@ -201,18 +699,37 @@ impl RegionIndex {
             district_to_city,
             cities,
             districts,
+            towns,
+            town_to_regions,
+            villages,
+            village_to_regions,
+            province_codes,
+            city_codes,
+            city_kinds,
+            district_codes,
+            code_to_region,
+            city_district_regions,
         }
     }
 
     /// 检查是否是直辖市
+    #[allow(dead_code)]
     pub fn is_municipality(&self, province: &str) -> bool {
         MUNICIPALITIES.contains(&province)
     }
 
+    /// 检查省级单位是否即为城市（直辖市、特别行政区），下一级直接为区县
+    pub fn is_province_level_city(&self, province: &str) -> bool {
+        matches!(
+            self.city_kinds.get(province),
+            Some(RegionKind::Municipality | RegionKind::SpecialAdministrativeRegion)
+        )
+    }
+
     /// 检查是否是不设区的市
     #[allow(dead_code)]
     pub fn is_no_district_city(&self, city: &str) -> bool {
-        NO_DISTRICT_CITIES.contains(&city)
+        self.city_kinds.get(city) == Some(&RegionKind::NoDistrictCity)
     }
 
     /// 根据城市查找省份
@@ -227,6 +744,44 @@ impl RegionIndex {
         self.district_to_city.get(district)
     }
 
+    /// 根据行政区划代码查找地区记录
+    pub fn find_region_by_code(&self, code: &str) -> Option<&Region> {
+        self.code_to_region.get(code)
+    }
+
+    /// 根据城市、区县查找地区记录
+    pub fn find_region(&self, city: &str, district: Option<&str>) -> Option<&Region> {
+        self.city_district_regions
+            .get(&(city.to_string(), district.unwrap_or_default().to_string()))
+    }
+
+    /// 查找属于指定城市（及区县）的乡镇街道记录
+    pub fn find_town(&self, town: &str, city: &str, district: Option<&str>) -> Vec<&Region> {
+        self.town_to_regions
+            .get(town)
+            .map(|regions| {
+                regions
+                    .iter()
+                    .filter(|r| r.city == city)
+                    .filter(|r| district.is_none() || r.district.as_deref() == district)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 查找属于指定城市、乡镇的村、社区记录
+    pub fn find_village(&self, village: &str, city: &str, town: &str) -> Vec<&Region> {
+        self.village_to_regions
+            .get(village)
+            .map(|regions| {
+                regions
+                    .iter()
+                    .filter(|r| r.city == city && r.town.as_deref() == Some(town))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// 验证区县是否属于某个城市
     pub fn validate_district(&self, city: &str, district: &str) -> bool {
         self.city_districts
@@ -248,6 +803,16 @@ mod tests {
         assert!(regions.len() > 3000);
     }
 
+    #[test]
+    fn test_parse_regions() {
+        assert!(parse_regions(PCA_DATA).is_ok());
+        let regions = parse_regions("country,sheng,shi,qu\n中国,广东省,深圳市,南山区\n\n").unwrap();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].district_code.as_deref(), Some("440305"));
+        let err = parse_regions("country,sheng,shi,qu\n中国,广东省").unwrap_err();
+        assert!(matches!(err, ParseError::DataLoadError(ref msg) if msg.starts_with("line 2")));
+    }
+
     #[test]
     fn test_province_aliases() {
         let aliases = province_aliases();
@@ -280,4 +845,261 @@ mod tests {
         assert!(index.is_no_district_city("东莞市"));
         assert!(!index.is_no_district_city("深圳市"));
     }
+
+    #[test]
+    fn test_classify_city() {
+        let index = RegionIndex::build(&load_regions());
+        let kind = |city: &str| index.city_kinds.get(city).copied();
+
+        assert_eq!(kind("北京市"), Some(RegionKind::Municipality));
+        assert_eq!(kind("深圳市"), Some(RegionKind::PrefectureCity));
+        assert_eq!(kind("东莞市"), Some(RegionKind::NoDistrictCity));
+        assert_eq!(
+            kind("大理白族自治州"),
+            Some(RegionKind::AutonomousPrefecture)
+        );
+        assert_eq!(kind("阿里地区"), Some(RegionKind::Prefecture));
+        assert_eq!(kind("锡林郭勒盟"), Some(RegionKind::League));
+        for city in ["仙桃市", "神农架林区", "济源市", "五指山市", "定安县"] {
+            assert_eq!(
+                kind(city),
+                Some(RegionKind::ProvinceAdministered),
+                "{}",
+                city
+            );
+        }
+        assert_eq!(kind("石河子市"), Some(RegionKind::CorpsCity));
+        assert_eq!(kind("儋州市"), Some(RegionKind::NoDistrictCity));
+        assert_eq!(kind("三沙市"), Some(RegionKind::PrefectureCity));
+        // 未收录代码的台湾省县市
+        assert_eq!(kind("台北市"), None);
+
+        // 未给出代码时按内置代码表和名称后缀判断
+        assert_eq!(
+            classify_city("湖北省", "仙桃市", None),
+            Some(RegionKind::ProvinceAdministered)
+        );
+        assert_eq!(
+            classify_city("新疆维吾尔自治区", "石河子市", None),
+            Some(RegionKind::CorpsCity)
+        );
+        assert_eq!(
+            classify_city("某省", "某某自治州", None),
+            Some(RegionKind::AutonomousPrefecture)
+        );
+        assert_eq!(classify_city("某省", "某某市", None), None);
+
+        // 自行构造、未设置类型的记录在建索引时补上
+        let index = RegionIndex::build(&[
+            Region::new("湖北省", "仙桃市", None),
+            Region::new("云南省", "大理白族自治州", Some("大理市".to_string())),
+        ]);
+        assert_eq!(
+            index.city_kinds.get("仙桃市"),
+            Some(&RegionKind::ProvinceAdministered)
+        );
+        assert_eq!(
+            index.city_kinds.get("大理白族自治州"),
+            Some(&RegionKind::AutonomousPrefecture)
+        );
+    }
+
+    #[test]
+    fn test_load_towns() {
+        let regions = load_regions();
+        // 不设区的市下级单位不作为区县加载
+        assert!(!regions
+            .iter()
+            .any(|r| r.city == "东莞市" && r.district.is_some()));
+
+        let mut all = regions;
+        all.extend(load_towns());
+        let csv = "sheng,shi,qu,zhen\n广东省,深圳市,南山区,粤海街道\n";
+        all.extend(parse_towns(csv).unwrap());
+        let index = RegionIndex::build(&all);
+
+        assert!(index.towns.contains("长安镇"));
+        assert!(!index.districts.contains("长安镇"));
+        assert_eq!(index.find_town("长安镇", "东莞市", None).len(), 1);
+        assert_eq!(
+            index.find_town("粤海街道", "深圳市", Some("南山区")).len(),
+            1
+        );
+        assert!(index
+            .find_town("粤海街道", "深圳市", Some("福田区"))
+            .is_empty());
+
+        assert!(parse_towns("sheng,shi,qu,zhen\n广东省,深圳市,南山区,\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_villages() {
+        let csv = "sheng,shi,qu,zhen,cun\n广东省,深圳市,南山区,粤海街道,大冲社区\n";
+        let villages = parse_villages(csv).unwrap();
+
+        let index = RegionIndex::build(&villages);
+        assert_eq!(
+            index.find_village("大冲社区", "深圳市", "粤海街道").len(),
+            1
+        );
+        assert!(index
+            .find_village("大冲社区", "深圳市", "南头街道")
+            .is_empty());
+
+        assert!(parse_villages("sheng,shi,qu,zhen,cun\n广东省,东莞市,,,乌沙社区\n").is_err());
+    }
+
+    #[test]
+    fn test_load_centroids() {
+        let centroids = load_centroids();
+        let key = |p: &str, c: &str, d: &str| (p.to_string(), c.to_string(), d.to_string());
+
+        // 每个省级行政区都有坐标
+        let index = RegionIndex::build(&load_regions());
+        for province in index.provinces.iter().filter(|p| *p != "国外") {
+            assert!(
+                centroids.contains_key(&key(province, "", "")),
+                "{}",
+                province
+            );
+        }
+
+        let sz = centroids[&key("广东省", "深圳市", "")];
+        assert!((113.0..115.0).contains(&sz.lng));
+        assert!((22.0..23.0).contains(&sz.lat));
+    }
+
+    #[test]
+    fn test_postal_info_match_regions() {
+        let index = RegionIndex::build(&load_regions());
+
+        let infos = load_postal_info();
+        for ((province, city, district), info) in &infos {
+            let district = Some(district.as_str()).filter(|d| !d.is_empty());
+            let region = index.find_region(city, district);
+            assert!(region.is_some_and(|r| &r.province == province), "{}", city);
+            if let Some(code) = &info.postal_code {
+                assert_eq!(code.len(), 6);
+            }
+            if let Some(code) = &info.area_code {
+                assert!(code.starts_with('0'));
+            }
+        }
+
+        // 大陆的地级单位基本都有电话区号
+        let missing = index
+            .province_cities
+            .iter()
+            .filter(|(p, _)| {
+                !matches!(
+                    p.as_str(),
+                    "国外" | "台湾省" | "香港特别行政区" | "澳门特别行政区"
+                )
+            })
+            .flat_map(|(p, cities)| {
+                cities
+                    .iter()
+                    .map(move |c| (p.clone(), c.clone(), String::new()))
+            })
+            .filter(|key| infos.get(key).is_none_or(|info| info.area_code.is_none()))
+            .count();
+        assert!(missing < 10, "{}", missing);
+    }
+
+    #[test]
+    fn test_history_successors_exist() {
+        // 承继地区必须是现行地区
+        let index = RegionIndex::build(&load_regions());
+        let history = load_history();
+        assert!(!history.is_empty());
+
+        for r in history {
+            assert!(index.find_region(&r.city, r.district.as_deref()).is_none());
+            assert!(
+                index
+                    .find_region(&r.new_city, r.new_district.as_deref())
+                    .is_some(),
+                "{}{:?}",
+                r.new_city,
+                r.new_district
+            );
+            let date: Vec<&str> = r.effective.split('-').collect();
+            assert!((1..=3).contains(&date.len()), "{}", r.effective);
+            assert!(r.effective_year() >= 2000, "{}", r.effective);
+        }
+    }
+
+    #[test]
+    fn test_sar_aliases_exist() {
+        let index = RegionIndex::build(&load_regions());
+        let aliases = load_sar_aliases();
+        assert!(!aliases.is_empty());
+
+        for (sar, district, alias) in aliases {
+            match district {
+                Some(d) => assert!(index.validate_district(sar, d), "{}{}", sar, d),
+                None => assert!(index.provinces.contains(sar), "{}", sar),
+            }
+            assert!(!alias.is_empty());
+        }
+        assert_eq!(index.city_districts["香港特别行政区"].len(), 18);
+        assert!(index.is_province_level_city("澳门特别行政区"));
+        assert!(index.is_province_level_city("北京市"));
+        assert!(!index.is_province_level_city("广东省"));
+    }
+
+    #[test]
+    fn test_adcodes_match_regions() {
+        // 代码表中的每条记录都必须能在地区数据中找到
+        let regions = load_regions();
+        let index = RegionIndex::build(&regions);
+
+        for ((province, city, district), code) in load_adcodes() {
+            assert_eq!(code.len(), 6, "{}", code);
+            if city.is_empty() {
+                assert_eq!(
+                    index.province_codes.get(province).map(|c| c.as_str()),
+                    Some(code)
+                );
+            } else if district.is_empty() {
+                assert_eq!(index.city_codes.get(city).map(|c| c.as_str()), Some(code));
+            } else {
+                let key = (city.to_string(), district.to_string());
+                assert_eq!(
+                    index.district_codes.get(&key).map(|c| c.as_str()),
+                    Some(code)
+                );
+            }
+        }
+
+        assert_eq!(
+            index.province_codes.get("广东省"),
+            Some(&"440000".to_string())
+        );
+        assert_eq!(index.city_codes.get("仙桃市"), Some(&"429004".to_string()));
+
+        // 大陆的区县都有代码，只有 2022 年以后新设及没有国标代码的单位除外
+        let missing: Vec<_> = regions
+            .iter()
+            .filter(|r| r.district.is_some() && r.district_code.is_none())
+            .filter(|r| {
+                !matches!(
+                    r.province_code.as_deref(),
+                    Some("710000" | "810000" | "820000")
+                )
+            })
+            .filter_map(|r| r.district.as_deref())
+            .collect();
+        assert_eq!(
+            missing,
+            [
+                "南沙区",
+                "西沙区",
+                "错那市",
+                "米林市",
+                "大柴旦行政委员会",
+                "加格达奇区"
+            ]
+        );
+    }
 }
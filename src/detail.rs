@@ -0,0 +1,329 @@
+//! 详细地址的结构化拆分：道路、门牌号、小区、楼栋、单元、楼层、房间
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 详细地址的各组成部分，见 [`ParsedAddress::detail_parts`](crate::ParsedAddress::detail_parts)
+///
+/// 道路、小区为完整名称；门牌号、楼栋、单元、楼层、房间为编号，不含"号"、"栋"等后缀。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DetailParts {
+    /// 道路、街巷，如"科技园路"、"深南大道"
+    ///
+    /// 只取第一条道路，之后出现的道路（如"与人民路交汇处"中的"人民路"）归入 `rest`。
+    pub road: Option<String>,
+    /// 门牌号，如"1号"中的"1"
+    pub number: Option<String>,
+    /// 小区、大厦等，如"阳光花园"、"腾讯大厦"
+    pub compound: Option<String>,
+    /// 楼栋，如"3栋"、"A座"、"2号楼"中的编号
+    pub building: Option<String>,
+    /// 单元
+    pub unit: Option<String>,
+    /// 楼层
+    pub floor: Option<String>,
+    /// 房间号
+    pub room: Option<String>,
+    /// 未能识别的其余部分
+    pub rest: String,
+}
+
+/// 详细地址组成部分的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Road,
+    Number,
+    Compound,
+    Building,
+    Unit,
+    Floor,
+    Room,
+}
+
+/// 编号后缀，较长的在前
+const NUMBER_SUFFIXES: &[(&str, Part)] = &[
+    ("号楼", Part::Building),
+    ("单元", Part::Unit),
+    ("号", Part::Number),
+    ("栋", Part::Building),
+    ("幢", Part::Building),
+    ("座", Part::Building),
+    ("层", Part::Floor),
+    ("楼", Part::Floor),
+    ("室", Part::Room),
+    ("房", Part::Room),
+    ("户", Part::Room),
+];
+
+/// 名称后缀，较长的在前
+const NAME_SUFFIXES: &[(&str, Part)] = &[
+    ("大道", Part::Road),
+    ("大街", Part::Road),
+    ("胡同", Part::Road),
+    ("小区", Part::Compound),
+    ("花园", Part::Compound),
+    ("家园", Part::Compound),
+    ("新村", Part::Compound),
+    ("大厦", Part::Compound),
+    ("大楼", Part::Compound),
+    ("公寓", Part::Compound),
+    ("广场", Part::Compound),
+    ("中心", Part::Compound),
+    ("路", Part::Road),
+    ("街", Part::Road),
+    ("道", Part::Road),
+    ("巷", Part::Road),
+    ("弄", Part::Road),
+    ("苑", Part::Compound),
+    ("园", Part::Compound),
+    ("村", Part::Compound),
+    ("城", Part::Compound),
+    ("府", Part::Compound),
+];
+
+impl DetailParts {
+    /// 拆分详细地址
+    ///
+    /// ```rust
+    /// use cpca::DetailParts;
+    ///
+    /// let parts = DetailParts::parse("科技园路1号阳光花园3栋2单元1201室");
+    /// assert_eq!(parts.road.as_deref(), Some("科技园路"));
+    /// assert_eq!(parts.number.as_deref(), Some("1"));
+    /// assert_eq!(parts.compound.as_deref(), Some("阳光花园"));
+    /// assert_eq!(parts.building.as_deref(), Some("3"));
+    /// assert_eq!(parts.unit.as_deref(), Some("2"));
+    /// assert_eq!(parts.room.as_deref(), Some("1201"));
+    /// ```
+    pub fn parse(detail: &str) -> DetailParts {
+        let mut parts = DetailParts::default();
+        let mut pos = 0;
+        while pos < detail.len() {
+            let text = &detail[pos..];
+            let part = numbered(text).map(|(part, value, len)| (Some(part), value, len));
+            let len = match part.or_else(|| named(text)) {
+                Some((part, value, len)) => {
+                    if !part.is_some_and(|part| parts.set(part, value)) {
+                        parts.rest.push_str(&text[..len]);
+                    }
+                    len
+                }
+                None => match bare_number(text) {
+                    Some(len) => {
+                        parts.set_bare(&text[..len]);
+                        len
+                    }
+                    None => {
+                        let ch = text.chars().next().map_or(1, char::len_utf8);
+                        parts.rest.push_str(&text[..ch]);
+                        ch
+                    }
+                },
+            };
+            pos += len;
+        }
+        parts.rest = parts.rest.trim().to_string();
+        parts
+    }
+
+    /// 是否什么都没有识别出
+    pub fn is_empty(&self) -> bool {
+        *self == DetailParts::default()
+    }
+
+    fn slot(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::Road => &mut self.road,
+            Part::Number => &mut self.number,
+            Part::Compound => &mut self.compound,
+            Part::Building => &mut self.building,
+            Part::Unit => &mut self.unit,
+            Part::Floor => &mut self.floor,
+            Part::Room => &mut self.room,
+        }
+    }
+
+    /// 填写尚未识别的部分，已有时返回 `false`
+    fn set(&mut self, part: Part, value: &str) -> bool {
+        let slot = self.slot(part);
+        if slot.is_some() {
+            return false;
+        }
+        *slot = Some(value.to_string());
+        true
+    }
+
+    /// 不带后缀的编号：楼栋之后为房间号，"3-2-1201" 为楼栋-单元-房间
+    fn set_bare(&mut self, text: &str) {
+        let pieces: Vec<&str> = text.split('-').collect();
+        let fields: &[Part] = match pieces.len() {
+            3 => &[Part::Building, Part::Unit, Part::Room],
+            2 => &[Part::Building, Part::Room],
+            _ if self.building.is_some() || self.unit.is_some() || self.floor.is_some() => {
+                &[Part::Room]
+            }
+            _ => &[],
+        };
+        let free = fields.iter().all(|&part| self.slot(part).is_none());
+        if fields.is_empty() || !free || pieces.iter().any(|p| p.is_empty()) {
+            self.rest.push_str(text);
+            return;
+        }
+        for (&part, value) in fields.iter().zip(pieces) {
+            self.set(part, value);
+        }
+    }
+}
+
+/// 开头的"编号 + 后缀"，返回（类型, 编号, 长度）
+///
+/// 东西南北只能作为编号的前缀（如"东3栋"），"北楼"、"南座"不是编号。
+fn numbered(text: &str) -> Option<(Part, &str, usize)> {
+    let prefix: usize = text
+        .chars()
+        .take_while(|&ch| is_direction(ch))
+        .map(char::len_utf8)
+        .sum();
+    let len: usize = text[prefix..]
+        .chars()
+        .take_while(|&ch| is_number_char(ch))
+        .map(char::len_utf8)
+        .sum();
+    let value = text[..prefix + len].trim_end_matches('-');
+    if value.len() <= prefix {
+        return None;
+    }
+    let rest = &text[value.len()..];
+    NUMBER_SUFFIXES.iter().find_map(|&(suffix, part)| {
+        rest.starts_with(suffix)
+            .then_some((part, value, value.len() + suffix.len()))
+    })
+}
+
+/// 开头不带后缀的编号（须含数字），返回长度
+fn bare_number(text: &str) -> Option<usize> {
+    let len: usize = text
+        .chars()
+        .take_while(|&ch| ch.is_ascii_alphanumeric() || ch == '-')
+        .map(char::len_utf8)
+        .sum();
+    let value = text[..len].trim_end_matches('-');
+    value
+        .contains(|ch: char| ch.is_ascii_digit())
+        .then_some(value.len())
+}
+
+/// 开头以道路、小区后缀结尾的名称，返回（类型, 名称, 长度），"XX街道"的类型为 `None`
+///
+/// 取最早出现的后缀，紧随其后的后缀一并计入，如"科技园路"、"中关村大街"为道路。
+fn named(text: &str) -> Option<(Option<Part>, &str, usize)> {
+    let mut found = None;
+    let mut pos = 0;
+    for ch in text.chars() {
+        if !is_han(ch) || (pos > 0 && numbered(&text[pos..]).is_some()) {
+            break;
+        }
+        pos += ch.len_utf8();
+        // 名称至少两个字
+        found = NAME_SUFFIXES
+            .iter()
+            .find(|(suffix, _)| text[..pos].ends_with(suffix) && pos > suffix.len())
+            .map(|&(_, part)| (part, pos));
+        if found.is_some() {
+            break;
+        }
+    }
+    let (mut part, mut len) = found?;
+    while let Some(&(suffix, next)) = NAME_SUFFIXES
+        .iter()
+        .find(|(suffix, _)| text[len..].starts_with(suffix))
+    {
+        part = next;
+        len += suffix.len();
+    }
+    // 乡镇级的街道，不是道路
+    let part = (!text[..len].ends_with("街道")).then_some(part);
+    Some((part, &text[..len], len))
+}
+
+/// 编号中可出现的字符
+fn is_number_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '-' || "一二三四五六七八九十百零〇甲乙丙丁之".contains(ch)
+}
+
+/// 可作为编号前缀的方位字
+fn is_direction(ch: char) -> bool {
+    "东西南北".contains(ch)
+}
+
+/// 是否为汉字
+pub(crate) fn is_han(ch: char) -> bool {
+    matches!(ch, '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let parts = DetailParts::parse("深南大道10000号腾讯大厦A座12楼");
+        assert_eq!(parts.road.as_deref(), Some("深南大道"));
+        assert_eq!(parts.number.as_deref(), Some("10000"));
+        assert_eq!(parts.compound.as_deref(), Some("腾讯大厦"));
+        assert_eq!(parts.building.as_deref(), Some("A"));
+        assert_eq!(parts.floor.as_deref(), Some("12"));
+        assert_eq!(parts.rest, "");
+
+        let parts = DetailParts::parse("幸福小区 5号楼3单元 502");
+        assert_eq!(parts.compound.as_deref(), Some("幸福小区"));
+        assert_eq!(parts.building.as_deref(), Some("5"));
+        assert_eq!(parts.unit.as_deref(), Some("3"));
+        assert_eq!(parts.room.as_deref(), Some("502"));
+
+        let parts = DetailParts::parse("望京西园三区3-2-1101");
+        assert_eq!(parts.building.as_deref(), Some("3"));
+        assert_eq!(parts.unit.as_deref(), Some("2"));
+        assert_eq!(parts.room.as_deref(), Some("1101"));
+
+        let parts = DetailParts::parse("东四十条胡同88-1号");
+        assert_eq!(parts.road.as_deref(), Some("东四十条胡同"));
+        assert_eq!(parts.number.as_deref(), Some("88-1"));
+
+        let parts = DetailParts::parse("中关村大街27号中关村大厦9层901");
+        assert_eq!(parts.road.as_deref(), Some("中关村大街"));
+        assert_eq!(parts.compound.as_deref(), Some("中关村大厦"));
+        assert_eq!(parts.floor.as_deref(), Some("9"));
+        assert_eq!(parts.room.as_deref(), Some("901"));
+
+        // 未匹配到的街道办、重复的道路归入其余部分
+        let parts = DetailParts::parse("龙华街道民治大道与人民路交汇处");
+        assert_eq!(parts.road.as_deref(), Some("民治大道"));
+        assert_eq!(parts.rest, "龙华街道与人民路交汇处");
+
+        // "院"不计入门牌号
+        let parts = DetailParts::parse("建设路甲1号院");
+        assert_eq!(parts.road.as_deref(), Some("建设路"));
+        assert_eq!(parts.number.as_deref(), Some("甲1"));
+        assert_eq!(parts.rest, "院");
+
+        // 方位字只作编号前缀
+        let parts = DetailParts::parse("北楼3层");
+        assert_eq!(parts.floor.as_deref(), Some("3"));
+        assert_eq!(parts.rest, "北楼");
+        let parts = DetailParts::parse("东楼");
+        assert_eq!(parts.floor, None);
+        assert_eq!(parts.rest, "东楼");
+        let parts = DetailParts::parse("南座");
+        assert_eq!(parts.building, None);
+        assert_eq!(parts.rest, "南座");
+        let parts = DetailParts::parse("东3栋");
+        assert_eq!(parts.building.as_deref(), Some("东3"));
+
+        let parts = DetailParts::parse("放在门卫室");
+        assert_eq!(parts.room, None);
+        assert_eq!(parts.rest, "放在门卫室");
+        assert!(DetailParts::parse("").is_empty());
+    }
+}
@@ -0,0 +1,351 @@
+//! 英文（拼音）地名匹配，用于解析由小到大排列的英文地址
+
+use crate::data::{load_english_names, Redirect, RegionIndex};
+use crate::pinyin::Pinyin;
+use crate::region::RegionLevel;
+use std::collections::HashMap;
+
+/// 行政区划的中文通名（长的在前）
+const GENERIC_SUFFIXES: [&str; 14] = [
+    "特别行政区",
+    "自治区",
+    "自治州",
+    "自治县",
+    "自治旗",
+    "新区",
+    "林区",
+    "地区",
+    "省",
+    "市",
+    "区",
+    "县",
+    "旗",
+    "盟",
+];
+
+/// 地名中的民族名称（自治地方名称中使用，不含"族"字）
+const ETHNIC_GROUPS: [&str; 55] = [
+    "蒙古",
+    "回",
+    "藏",
+    "维吾尔",
+    "苗",
+    "彝",
+    "壮",
+    "布依",
+    "朝鲜",
+    "满",
+    "侗",
+    "瑶",
+    "白",
+    "土家",
+    "哈尼",
+    "哈萨克",
+    "傣",
+    "黎",
+    "傈僳",
+    "佤",
+    "畲",
+    "高山",
+    "拉祜",
+    "水",
+    "东乡",
+    "纳西",
+    "景颇",
+    "柯尔克孜",
+    "土",
+    "达斡尔",
+    "仫佬",
+    "羌",
+    "布朗",
+    "撒拉",
+    "毛南",
+    "仡佬",
+    "锡伯",
+    "阿昌",
+    "普米",
+    "塔吉克",
+    "怒",
+    "乌孜别克",
+    "俄罗斯",
+    "鄂温克",
+    "德昂",
+    "保安",
+    "裕固",
+    "京",
+    "塔塔尔",
+    "独龙",
+    "鄂伦春",
+    "赫哲",
+    "门巴",
+    "珞巴",
+    "基诺",
+];
+
+/// 英文通名及其对应的中文通名（长的在前）
+const ENGLISH_SUFFIXES: [(&str, &[&str]); 18] = [
+    ("special administrative region", &["特别行政区"]),
+    ("autonomous prefecture", &["自治州"]),
+    ("autonomous region", &["自治区"]),
+    ("autonomous county", &["自治县"]),
+    ("autonomous banner", &["自治旗"]),
+    ("forestry district", &["林区"]),
+    ("new district", &["新区"]),
+    ("new area", &["新区"]),
+    ("municipality", &["市"]),
+    ("prefecture", &["地区", "自治州", "市"]),
+    ("province", &["省"]),
+    ("parish", &["堂区"]),
+    ("district", &["区"]),
+    ("county", &["县"]),
+    ("banner", &["旗"]),
+    ("league", &["盟"]),
+    ("city", &["市"]),
+    ("sar", &["特别行政区"]),
+];
+
+/// 各级英文地名 -> 规范名称
+pub(crate) struct EnglishNames {
+    provinces: HashMap<String, Vec<String>>,
+    cities: HashMap<String, Vec<String>>,
+    districts: HashMap<String, Vec<String>>,
+}
+
+impl EnglishNames {
+    /// 以拼音（去掉通名、民族名称）为英文专名，并加入官方译名
+    ///
+    /// 已撤销或更名的城市、区县（如 "Xiangfan"）同样收录，解析时按调整记录换算。
+    pub fn build(index: &RegionIndex, history: &[Redirect]) -> Self {
+        let mut names = Self {
+            provinces: HashMap::new(),
+            cities: HashMap::new(),
+            districts: HashMap::new(),
+        };
+
+        for province in &index.provinces {
+            names.add_pinyin_keys(RegionLevel::Province, province);
+        }
+        for city in &index.cities {
+            names.add_pinyin_keys(RegionLevel::City, city);
+        }
+        for district in &index.districts {
+            names.add_pinyin_keys(RegionLevel::District, district);
+        }
+        for redirect in history {
+            if !index.cities.contains(&redirect.city) {
+                names.add_pinyin_keys(RegionLevel::City, &redirect.city);
+            }
+            if let Some(ref district) = redirect.district {
+                names.add_pinyin_keys(RegionLevel::District, district);
+            }
+        }
+
+        for (name, english) in load_english_names() {
+            let level = if index.provinces.contains(name) {
+                RegionLevel::Province
+            } else if index.cities.contains(name) {
+                RegionLevel::City
+            } else {
+                RegionLevel::District
+            };
+            let key = compact(english);
+            // 有官方译名的地名不再占用同音的拼音，如 "Shanxi" 只指山西省（陕西省为 "Shaanxi"）
+            for (other, regions) in names.map_mut(level) {
+                if *other != key && regions.len() > 1 {
+                    regions.retain(|n| n != name);
+                }
+            }
+            names.add(level, key, name);
+        }
+
+        names
+    }
+
+    /// 在指定层级查找英文地名，如 "Nanshan District"、"Shenzhen"
+    ///
+    /// 带英文通名时只返回通名相符的地名，没有相符的再按整体查找官方译名。
+    pub fn lookup(&self, level: RegionLevel, token: &str) -> Vec<String> {
+        let lower = token.trim().to_ascii_lowercase();
+        let (name, suffixes) = ENGLISH_SUFFIXES
+            .iter()
+            .find_map(|(english, chinese)| {
+                lower
+                    .strip_suffix(english)
+                    .filter(|rest| rest.is_empty() || rest.ends_with(' ') || rest.ends_with('-'))
+                    .map(|rest| (rest, Some(*chinese)))
+            })
+            .unwrap_or((lower.as_str(), None));
+
+        let map = match level {
+            RegionLevel::Province => &self.provinces,
+            RegionLevel::City => &self.cities,
+            RegionLevel::District => &self.districts,
+        };
+        let names: Vec<String> = map
+            .get(&compact(name))
+            .into_iter()
+            .flatten()
+            .filter(|n| suffixes.is_none_or(|s| s.iter().any(|s| n.ends_with(s))))
+            .cloned()
+            .collect();
+        if names.is_empty() && suffixes.is_some() {
+            // 通名是官方译名的一部分，如 "Kowloon City"
+            return map.get(&compact(&lower)).cloned().unwrap_or_default();
+        }
+        names
+    }
+
+    fn map_mut(&mut self, level: RegionLevel) -> &mut HashMap<String, Vec<String>> {
+        match level {
+            RegionLevel::Province => &mut self.provinces,
+            RegionLevel::City => &mut self.cities,
+            RegionLevel::District => &mut self.districts,
+        }
+    }
+
+    fn add_pinyin_keys(&mut self, level: RegionLevel, name: &str) {
+        let base = strip_generic(name);
+        let mut keys = vec![name.to_string(), base.to_string(), base.replace('族', "")];
+        keys.push(strip_ethnic(base).to_string());
+        for key in keys {
+            self.add(level, Pinyin::from_hanzi(&key).full, name);
+        }
+    }
+
+    fn add(&mut self, level: RegionLevel, key: String, name: &str) {
+        let names = self.map_mut(level).entry(key).or_default();
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+}
+
+/// 去掉中文通名，剩余部分至少保留两个字
+fn strip_generic(name: &str) -> &str {
+    GENERIC_SUFFIXES
+        .iter()
+        .filter_map(|suffix| name.strip_suffix(suffix))
+        .find(|rest| rest.chars().count() >= 2)
+        .unwrap_or(name)
+}
+
+/// 去掉末尾的民族名称（如"大理白族" -> "大理"），剩余部分至少保留两个字
+fn strip_ethnic(mut name: &str) -> &str {
+    'outer: loop {
+        for group in ETHNIC_GROUPS {
+            let rest = name
+                .strip_suffix('族')
+                .and_then(|n| n.strip_suffix(group))
+                .or_else(|| {
+                    // 多字民族名称在地名中可省略"族"字，如"巴音郭楞蒙古"
+                    (group.chars().count() >= 2)
+                        .then(|| name.strip_suffix(group))
+                        .flatten()
+                });
+            if let Some(rest) = rest.filter(|r| r.chars().count() >= 2) {
+                name = rest;
+                continue 'outer;
+            }
+        }
+        return name;
+    }
+}
+
+/// 英文地名的紧凑形式：小写字母，去掉空格、连字符、撇号和 "and"
+fn compact(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| !word.eq_ignore_ascii_case("and"))
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// 是否为国家名称
+pub(crate) fn is_country(token: &str) -> bool {
+    matches!(
+        compact(token).as_str(),
+        "china" | "cn" | "prc" | "prchina" | "peoplesrepublicofchina"
+    )
+}
+
+/// 是否为港澳片区名称（如 "Kowloon"、"New Territories"），不是行政区划
+pub(crate) fn is_sar_area(token: &str) -> bool {
+    matches!(
+        compact(token).as_str(),
+        "hongkongisland"
+            | "kowloon"
+            | "newterritories"
+            | "nt"
+            | "macaupeninsula"
+            | "macaopeninsula"
+    )
+}
+
+/// 去掉末尾的 6 位邮政编码，如 "Guangdong 518057"
+pub(crate) fn strip_postal_code(token: &str) -> &str {
+    let trimmed = token.trim_end();
+    match trimmed.char_indices().rev().nth(5) {
+        Some((pos, _))
+            if trimmed[pos..].chars().all(|c| c.is_ascii_digit())
+                && !trimmed[..pos].ends_with(|c: char| c.is_ascii_digit()) =>
+        {
+            trimmed[..pos].trim_end()
+        }
+        _ => trimmed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{load_history, load_regions};
+
+    #[test]
+    fn test_strip_names() {
+        assert_eq!(strip_generic("南山区"), "南山");
+        assert_eq!(strip_generic("城区"), "城区");
+        assert_eq!(strip_ethnic(strip_generic("大理白族自治州")), "大理");
+        assert_eq!(strip_ethnic(strip_generic("湘西土家族苗族自治州")), "湘西");
+        assert_eq!(
+            strip_ethnic(strip_generic("巴音郭楞蒙古自治州")),
+            "巴音郭楞"
+        );
+        assert_eq!(strip_ethnic(strip_generic("东乡族自治县")), "东乡族");
+    }
+
+    #[test]
+    fn test_lookup() {
+        let names = EnglishNames::build(&RegionIndex::build(&load_regions()), &load_history());
+
+        assert_eq!(names.lookup(RegionLevel::Province, "Guangdong"), ["广东省"]);
+        assert_eq!(
+            names.lookup(RegionLevel::Province, "Inner Mongolia"),
+            ["内蒙古自治区"]
+        );
+        assert_eq!(names.lookup(RegionLevel::City, "Xi'an"), ["西安市"]);
+        // 官方译名优先于同音拼音
+        assert_eq!(names.lookup(RegionLevel::Province, "Shanxi"), ["山西省"]);
+        assert_eq!(names.lookup(RegionLevel::Province, "Shaanxi"), ["陕西省"]);
+        assert_eq!(
+            names.lookup(RegionLevel::City, "Dali Bai Autonomous Prefecture"),
+            ["大理白族自治州"]
+        );
+        // 朝阳区、潮阳区同音
+        let mut chaoyang = names.lookup(RegionLevel::District, "Chaoyang District");
+        chaoyang.sort();
+        assert_eq!(chaoyang, ["朝阳区", "潮阳区"]);
+        assert!(names
+            .lookup(RegionLevel::District, "Nanshan County")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_postal_code_and_country() {
+        assert_eq!(strip_postal_code("Guangdong 518057"), "Guangdong");
+        assert_eq!(strip_postal_code("518057"), "");
+        assert_eq!(strip_postal_code("Room 1101"), "Room 1101");
+        assert!(is_country("P.R. China"));
+        assert!(!is_country("Chinatown"));
+        assert!(is_sar_area("New Territories"));
+        assert!(!is_sar_area("Kowloon City"));
+    }
+}
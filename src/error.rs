@@ -1,9 +1,11 @@
 //! 错误类型定义
 
+use crate::region::{Region, RegionLevel};
 use thiserror::Error;
 
 /// 地址解析错误
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
 pub enum ParseError {
     /// 数据加载失败
     #[error("Failed to load region data: {0}")]
@@ -16,4 +18,53 @@ pub enum ParseError {
     /// 未找到匹配的地区
     #[error("No matching region found for: {0}")]
     NotFound(String),
+
+    /// 同名区县无法确定所属城市，附全部候选
+    #[error("Ambiguous district {district}: {} candidates", candidates.len())]
+    AmbiguousDistrict {
+        /// 区县名称
+        district: String,
+        /// 可能的地区
+        candidates: Vec<Region>,
+    },
+
+    /// 城市不属于地址中写明的省份
+    #[error("City {city} is not in province {province}")]
+    CityNotInProvince {
+        /// 省份
+        province: String,
+        /// 城市
+        city: String,
+    },
+
+    /// 区县不属于地址中写明的城市
+    #[error("District {district} is not in city {city}")]
+    DistrictNotInCity {
+        /// 城市
+        city: String,
+        /// 区县
+        district: String,
+    },
+
+    /// 区县不属于地址中写明的省份
+    #[error("District {district} is not in province {province}")]
+    DistrictNotInProvince {
+        /// 省份
+        province: String,
+        /// 区县
+        district: String,
+    },
+
+    /// 没有该年份的行政区划数据（需启用 `vintages` 特性，支持的年份见 `VINTAGES`）
+    #[error("Unsupported dataset vintage: {0}")]
+    UnsupportedVintage(u16),
+
+    /// 缺少城市或区县
+    #[error("Incomplete address, missing {missing:?}: {address}")]
+    Incomplete {
+        /// 原始地址
+        address: String,
+        /// 缺少的层级（城市或区县）
+        missing: RegionLevel,
+    },
 }
@@ -0,0 +1,96 @@
+//! 繁简转换：按字把繁体地址折叠为简体用于匹配，并可把地名输出为繁体
+//!
+//! 对照表只收录地名及常见地址用字，逐字转换且不改变字节长度，
+//! 因此折叠后文本中的位置可直接用于原文。
+
+use crate::data::load_fanti;
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// 全局繁简字对照表
+static FANTI_TABLE: Lazy<FantiTable> = Lazy::new(FantiTable::load);
+
+struct FantiTable {
+    /// 繁体字 -> 简体字
+    to_jianti: HashMap<char, char>,
+    /// 简体字 -> 首选繁体字
+    to_fanti: HashMap<char, char>,
+}
+
+impl FantiTable {
+    fn load() -> Self {
+        let mut to_jianti = HashMap::new();
+        let mut to_fanti = HashMap::new();
+        for (fanti, jianti) in load_fanti() {
+            if fanti != jianti {
+                to_jianti.insert(fanti, jianti);
+            }
+            to_fanti.entry(jianti).or_insert(fanti);
+        }
+        Self {
+            to_jianti,
+            to_fanti,
+        }
+    }
+}
+
+fn convert<'a>(text: &'a str, table: &HashMap<char, char>) -> Cow<'a, str> {
+    if !text.chars().any(|c| table.contains_key(&c)) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.chars().map(|c| *table.get(&c).unwrap_or(&c)).collect())
+}
+
+/// 繁体字折叠为简体，没有繁体字时不复制
+pub(crate) fn to_jianti(text: &str) -> Cow<'_, str> {
+    convert(text, &FANTI_TABLE.to_jianti)
+}
+
+/// 简体地名转换为繁体，如"九龙城区" -> "九龍城區"
+pub(crate) fn to_fanti(text: &str) -> String {
+    convert(text, &FANTI_TABLE.to_fanti).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{load_regions, load_sar_aliases};
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(to_jianti("廣東省深圳市南山區"), "广东省深圳市南山区");
+        assert_eq!(to_jianti("臺北市"), "台北市");
+        assert!(matches!(to_jianti("广东省"), Cow::Borrowed(_)));
+        assert_eq!(to_fanti("内蒙古自治区"), "內蒙古自治區");
+        // 一简对多繁的字取地名中的写法
+        assert_eq!(to_fanti("台州市"), "台州市");
+        assert_eq!(to_fanti("钟祥市"), "鍾祥市");
+    }
+
+    #[test]
+    fn test_fanti_table() {
+        let regions = load_regions();
+        let names: String = regions
+            .iter()
+            .flat_map(|r| [&r.province, &r.city].into_iter().chain(&r.district))
+            .map(String::as_str)
+            .collect();
+        for (fanti, jianti) in load_fanti() {
+            // 逐字转换不改变字节长度
+            assert_eq!(fanti.len_utf8(), jianti.len_utf8(), "{}", fanti);
+            // 繁体字不能是简体地名用字，否则会误折叠
+            if fanti != jianti {
+                assert!(!names.contains(fanti), "{}", fanti);
+            }
+        }
+
+        // 与港澳地名的繁体写法一致
+        for (sar, district, alias) in load_sar_aliases() {
+            let name = district.unwrap_or(sar);
+            if to_jianti(alias) == name {
+                assert_eq!(to_fanti(name), alias);
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! 地名纠错：按编辑距离（可选同音字）模糊匹配，用于手工录入的错别字
+
+use crate::pinyin::is_homophone;
+use crate::trie::Trie;
+
+/// 模糊匹配选项，见 [`AddressParser::with_fuzzy`](crate::AddressParser::with_fuzzy)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyOptions {
+    /// 允许的最大编辑距离；此外每 3 个字最多 1 处错误
+    pub max_distance: usize,
+    /// 是否把同音字（不计声调）视为相同，如"杭洲" -> "杭州"
+    pub homophones: bool,
+}
+
+impl Default for FuzzyOptions {
+    fn default() -> Self {
+        Self {
+            max_distance: 1,
+            homophones: true,
+        }
+    }
+}
+
+impl FuzzyOptions {
+    /// 在文本开头模糊查找地名，返回 (匹配的文本, 规范名称, 匹配长度)
+    ///
+    /// `accept` 根据规范名称及其后的文本做层级校验。取距离最小、匹配最长的结果，
+    /// 对应多个地名时视为无法纠正。
+    pub(crate) fn find<'a, 't>(
+        &self,
+        trie: &'a Trie<String>,
+        text: &'t str,
+        accept: impl Fn(&String, &str) -> bool,
+    ) -> Option<(&'t str, &'a String, usize)> {
+        let homophones = self.homophones;
+        let mut matches: Vec<_> = trie
+            .find_fuzzy_prefixes(text, self.max_distance, |a, b| {
+                homophones && is_homophone(a, b)
+            })
+            .into_iter()
+            .filter(|m| m.word_len >= 2 && m.distance * 3 <= m.word_len)
+            .filter(|m| accept(m.value, &text[m.len..]))
+            .collect();
+        matches.sort_by_key(|m| (m.distance, usize::MAX - m.len));
+        let best = matches.first()?;
+        let mut names = matches
+            .iter()
+            .filter(|m| (m.distance, m.len) == (best.distance, best.len))
+            .map(|m| m.value);
+        names
+            .all(|name| name == best.value)
+            .then(|| (&text[..best.len], best.value, best.len))
+    }
+}
@@ -0,0 +1,86 @@
+//! 坐标定义与坐标系转换
+
+use std::f64::consts::PI;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 克拉索夫斯基椭球长半轴
+const KRASOVSKY_A: f64 = 6378245.0;
+/// 克拉索夫斯基椭球第一偏心率平方
+const KRASOVSKY_EE: f64 = 0.006_693_421_622_965_943;
+
+/// 经纬度坐标（WGS-84）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Coordinate {
+    /// 经度
+    pub lng: f64,
+    /// 纬度
+    pub lat: f64,
+}
+
+impl Coordinate {
+    /// 创建坐标
+    pub fn new(lng: f64, lat: f64) -> Self {
+        Self { lng, lat }
+    }
+
+    /// 转换为 GCJ-02（国测局）坐标，境外坐标保持不变
+    pub fn to_gcj02(&self) -> Coordinate {
+        if self.is_out_of_china() {
+            return *self;
+        }
+
+        let mut dlat = transform_lat(self.lng - 105.0, self.lat - 35.0);
+        let mut dlng = transform_lng(self.lng - 105.0, self.lat - 35.0);
+        let rad_lat = self.lat / 180.0 * PI;
+        let magic = 1.0 - KRASOVSKY_EE * rad_lat.sin() * rad_lat.sin();
+        let sqrt_magic = magic.sqrt();
+        dlat = (dlat * 180.0) / ((KRASOVSKY_A * (1.0 - KRASOVSKY_EE)) / (magic * sqrt_magic) * PI);
+        dlng = (dlng * 180.0) / (KRASOVSKY_A / sqrt_magic * rad_lat.cos() * PI);
+
+        Coordinate::new(self.lng + dlng, self.lat + dlat)
+    }
+
+    /// 粗略判断是否在中国境外（境外不做偏移）
+    fn is_out_of_china(&self) -> bool {
+        !(72.004..=137.8347).contains(&self.lng) || !(0.8293..=55.8271).contains(&self.lat)
+    }
+}
+
+fn transform_lat(x: f64, y: f64) -> f64 {
+    let mut ret = -100.0 + 2.0 * x + 3.0 * y + 0.2 * y * y + 0.1 * x * y + 0.2 * x.abs().sqrt();
+    ret += (20.0 * (6.0 * x * PI).sin() + 20.0 * (2.0 * x * PI).sin()) * 2.0 / 3.0;
+    ret += (20.0 * (y * PI).sin() + 40.0 * (y / 3.0 * PI).sin()) * 2.0 / 3.0;
+    ret += (160.0 * (y / 12.0 * PI).sin() + 320.0 * (y * PI / 30.0).sin()) * 2.0 / 3.0;
+    ret
+}
+
+fn transform_lng(x: f64, y: f64) -> f64 {
+    let mut ret = 300.0 + x + 2.0 * y + 0.1 * x * x + 0.1 * x * y + 0.1 * x.abs().sqrt();
+    ret += (20.0 * (6.0 * x * PI).sin() + 20.0 * (2.0 * x * PI).sin()) * 2.0 / 3.0;
+    ret += (20.0 * (x * PI).sin() + 40.0 * (x / 3.0 * PI).sin()) * 2.0 / 3.0;
+    ret += (150.0 * (x / 12.0 * PI).sin() + 300.0 * (x / 30.0 * PI).sin()) * 2.0 / 3.0;
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_gcj02() {
+        // 北京天安门附近，GCJ-02 偏移约为东 600 米、北 150 米
+        let wgs = Coordinate::new(116.397, 39.908);
+        let gcj = wgs.to_gcj02();
+        assert!((0.005..0.008).contains(&(gcj.lng - wgs.lng)));
+        assert!((0.0005..0.003).contains(&(gcj.lat - wgs.lat)));
+    }
+
+    #[test]
+    fn test_to_gcj02_out_of_china() {
+        let tokyo = Coordinate::new(139.69, 35.69);
+        assert_eq!(tokyo.to_gcj02(), tokyo);
+    }
+}
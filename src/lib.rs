@@ -8,6 +8,17 @@
 //! - 支持省份简称（如 "广东" -> "广东省"）
 //! - 支持直辖市特殊处理
 //! - 支持不设区的地级市（东莞、中山、儋州、嘉峪关）
+//! - 区分省直辖县级行政单位、兵团市、自治州、地区、盟等城市类型（`RegionKind`）
+//! - 支持拼音全拼、首字母输入（`AddressParser::with_pinyin`）
+//! - 支持繁体地址（内置繁简对照，无需 OpenCC），可输出繁体地名
+//! - 输入预处理：全角转半角、去除零宽字符，跳过地名之间的空格和分隔符
+//! - 可选的地名纠错，容忍错别字、同音字（`AddressParser::with_fuzzy`）
+//! - 从聊天记录、OCR 结果等文本中提取地址及其位置（`AddressParser::extract`）
+//! - 拆分快递单收件信息：姓名、电话、邮编和地址（`AddressParser::parse_recipient`）
+//! - 详细地址结构化：道路、门牌号、小区、楼栋、单元、楼层、房间（`ParsedAddress::detail_parts`）
+//! - 自定义各级地名别称（如"魔都"、"鹏城"），可从类 TOML 格式的文件读取（`Aliases`）
+//! - 按省、市、区县逐级浏览行政区划树，顺序固定（`AddressParser::province_nodes`）
+//! - 全局解析器可热加载（`reload_global`），读取无锁
 //! - 内置 2025 年最新行政区划数据（3600+ 条记录）
 //!
 //! ## 快速开始
@@ -33,15 +44,42 @@
 //! assert_eq!(full, "广东省深圳市");
 //! ```
 
+mod alias;
+mod builder;
 mod data;
+mod detail;
+mod english;
 mod error;
+mod fanti;
+mod fuzzy;
+mod geo;
 mod parser;
+mod pinyin;
+mod preprocess;
+mod recipient;
 mod region;
+mod tree;
 mod trie;
+#[cfg(feature = "vintages")]
+mod vintage;
 
+pub use alias::{Alias, Aliases};
+pub use builder::AddressParserBuilder;
+pub use detail::DetailParts;
 pub use error::ParseError;
+pub use fuzzy::FuzzyOptions;
+pub use geo::Coordinate;
 pub use parser::AddressParser;
-pub use region::{ParsedAddress, Region};
+pub use pinyin::Pinyin;
+pub use preprocess::{PreprocessOptions, Preprocessed};
+pub use recipient::{Phone, PhoneKind, Recipient};
+pub use region::{
+    Candidate, CandidateReason, ExtractedAddress, HistoricalName, MatchSource, ParsedAddress,
+    Provenance, Region, RegionKind, RegionLevel, Span, Spans,
+};
+pub use tree::RegionNode;
+#[cfg(feature = "vintages")]
+pub use vintage::{diff, RegionChange, CURRENT_VINTAGE, VINTAGES};
 
 /// 便捷函数：使用全局解析器解析地址
 ///
@@ -50,7 +88,7 @@ pub use region::{ParsedAddress, Region};
 /// assert_eq!(result.province, Some("北京市".to_string()));
 /// ```
 pub fn parse(address: &str) -> ParsedAddress {
-    AddressParser::global().parse(address)
+    AddressParser::with_global(|parser| parser.parse(address))
 }
 
 /// 便捷函数：标准化地址
@@ -64,7 +102,25 @@ pub fn normalize(
     city: impl AsRef<str>,
     district: Option<&str>,
 ) -> String {
-    AddressParser::global().normalize(province, city, district)
+    AddressParser::with_global(|parser| parser.normalize(province, city, district))
+}
+
+/// 用构建器重新构建全局解析器并整体替换，供 [`parse`]、[`normalize`] 等便捷函数使用
+///
+/// 适合长期运行的服务在数据或别称更新后热加载：构建失败时返回错误，全局实例保持不变；
+/// 替换是原子的，进行中的解析继续使用旧实例，读取全局实例始终无锁。
+///
+/// ```rust
+/// use cpca::{AddressParser, Aliases, RegionLevel};
+///
+/// let mut aliases = Aliases::new();
+/// aliases.add(RegionLevel::City, "鹏城", "深圳市");
+/// cpca::reload_global(AddressParser::builder().aliases(aliases)).unwrap();
+/// assert_eq!(cpca::parse("鹏城南山").district, Some("南山区".to_string()));
+/// ```
+pub fn reload_global(builder: AddressParserBuilder) -> Result<(), ParseError> {
+    AddressParser::set_global(builder.build()?);
+    Ok(())
 }
 
 #[cfg(test)]
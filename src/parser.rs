@@ -1,13 +1,43 @@
 //! 地址解析器核心实现
 
-use crate::data::{load_regions, province_aliases, RegionIndex};
-use crate::region::ParsedAddress;
+use crate::alias::{Alias, Aliases};
+use crate::builder::{AddressParserBuilder, MatchOptions};
+use crate::data::{
+    load_centroids, load_history, load_postal_info, load_regions, load_sar_aliases,
+    province_aliases, with_towns, PostalInfo, Redirect, RegionIndex, SAR_AREAS,
+};
+use crate::english::{is_country, is_sar_area, strip_postal_code, EnglishNames};
+use crate::error::ParseError;
+use crate::fanti::to_jianti;
+use crate::fuzzy::FuzzyOptions;
+use crate::geo::Coordinate;
+use crate::pinyin::{PinyinInput, PinyinTries};
+use crate::preprocess::{is_separator, PreprocessOptions};
+use crate::recipient::{find_numbers, is_name_like, mask, strip_label, Label, Number, Recipient};
+use crate::region::{
+    Candidate, CandidateReason, ExtractedAddress, HistoricalName, MatchSource, ParsedAddress,
+    Region, RegionLevel, Span,
+};
+use crate::tree::RegionNode;
 use crate::trie::Trie;
-use once_cell::sync::Lazy;
-use std::collections::HashMap;
+#[cfg(feature = "vintages")]
+use crate::vintage::vintage_regions;
+use arc_swap::ArcSwap;
+use once_cell::sync::{Lazy, OnceCell};
+use std::collections::{BTreeMap, HashMap};
+use std::iter;
+use std::ops::Range;
+use std::sync::Arc;
 
-/// 全局解析器实例
-static GLOBAL_PARSER: Lazy<AddressParser> = Lazy::new(AddressParser::new);
+/// 拼音解析的候选组合：(省份, 城市, 区县)
+type RegionCandidate = (String, Option<String>, Option<String>);
+
+/// 用内置数据构建的初始全局实例
+static DEFAULT_PARSER: Lazy<Arc<AddressParser>> = Lazy::new(|| Arc::new(AddressParser::new()));
+
+/// 全局解析器实例，可通过 [`crate::reload_global`] 整体替换
+static GLOBAL_PARSER: Lazy<ArcSwap<AddressParser>> =
+    Lazy::new(|| ArcSwap::new(Arc::clone(&DEFAULT_PARSER)));
 
 /// 地址解析器
 ///
@@ -19,18 +49,110 @@ pub struct AddressParser {
     city_trie: Trie<String>,
     /// 区县前缀树（含简称）
     district_trie: Trie<String>,
+    /// 乡镇街道前缀树（仅全称）
+    town_trie: Trie<String>,
+    /// 村、社区前缀树（含"村委会"等写法）
+    village_trie: Trie<String>,
+    /// 限定上级的自定义别称 (层级, 上级) -> 前缀树
+    scoped_aliases: HashMap<(RegionLevel, String), Trie<String>>,
+    /// 已撤销、更名的城市前缀树（含简称）
+    history_city_trie: Trie<String>,
+    /// 已撤销、更名的区县前缀树（含简称）
+    history_district_trie: Trie<String>,
+    /// 历史行政区划调整记录
+    history: Vec<Redirect>,
+    /// 拼音前缀树（全拼与首字母，按需构建）
+    pinyin_tries: Option<PinyinTries>,
+    /// 模糊匹配选项（默认关闭）
+    fuzzy: Option<FuzzyOptions>,
+    /// 输入预处理选项
+    preprocess: PreprocessOptions,
+    /// 没有省份时，开头既是城市又是区县的地名优先按区县解析
+    prefer_district: bool,
+    /// 英文地名表（首次调用 `parse_en` 时构建）
+    english_names: OnceCell<EnglishNames>,
     /// 区域索引
     index: RegionIndex,
     /// 省份简称映射
     province_aliases: HashMap<&'static str, &'static str>,
+    /// 中心点坐标 (省份, 城市, 区县) -> WGS-84 坐标
+    centroids: HashMap<(String, String, String), Coordinate>,
+    /// 邮政编码、电话区号 (城市, 区县) -> 编码
+    postal_info: HashMap<(String, String, String), PostalInfo>,
 }
 
 impl AddressParser {
     /// 创建新的解析器实例
     pub fn new() -> Self {
-        let regions = load_regions();
+        Self::from_regions(
+            with_towns(load_regions()),
+            load_history(),
+            MatchOptions::default(),
+        )
+    }
+
+    /// 按指定年份的行政区划创建解析器（需启用 `vintages` 特性）
+    ///
+    /// 支持的年份见 [`VINTAGES`](crate::VINTAGES)，其余年份返回
+    /// [`ParseError::UnsupportedVintage`]。历史名称换算只使用该年份之前生效、
+    /// 且承继地区在该年份存在的调整记录。
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::with_vintage(2009).unwrap();
+    /// let result = parser.parse("北京市崇文区");
+    /// assert_eq!(result.district, Some("崇文区".to_string()));
+    /// assert!(!result.is_historical());
+    /// ```
+    #[cfg(feature = "vintages")]
+    pub fn with_vintage(year: u16) -> Result<Self, ParseError> {
+        let regions = vintage_regions(year)?;
+        let keys: std::collections::HashSet<_> = regions
+            .iter()
+            .map(|r| (&r.province, &r.city, r.district.as_ref()))
+            .collect();
+        let history = load_history()
+            .into_iter()
+            .filter(|r| {
+                r.effective_year() <= year
+                    && keys.contains(&(&r.province, &r.new_city, r.new_district.as_ref()))
+            })
+            .collect();
+        Ok(Self::from_regions(
+            with_towns(regions),
+            history,
+            MatchOptions::default(),
+        ))
+    }
+
+    /// 创建构建器，可自定义行政区划数据及匹配行为
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::builder().short_names(false).build().unwrap();
+    /// assert_eq!(parser.parse("深圳南山").city, None);
+    /// assert_eq!(parser.parse("深圳市南山区").district, Some("南山区".to_string()));
+    /// ```
+    pub fn builder() -> AddressParserBuilder {
+        AddressParserBuilder::new()
+    }
+
+    /// 由行政区划记录（含乡镇街道）和历史调整记录构建解析器
+    pub(crate) fn from_regions(
+        regions: Vec<Region>,
+        history: Vec<Redirect>,
+        options: MatchOptions,
+    ) -> Self {
         let index = RegionIndex::build(&regions);
         let aliases = province_aliases();
+        // 按选项决定是否收录某个简称
+        let alias_ok = |short: &str| {
+            options.short_names
+                && !short.is_empty()
+                && short.chars().count() >= options.min_alias_len
+        };
 
         // 构建省份前缀树
         let mut province_trie = Trie::new();
@@ -38,7 +160,7 @@ impl AddressParser {
             province_trie.insert(province, province.clone());
             // 添加简称
             for (short, full) in &aliases {
-                if *full == province {
+                if *full == province && alias_ok(short) {
                     province_trie.insert(short, province.clone());
                 }
             }
@@ -51,7 +173,9 @@ impl AddressParser {
             // 添加简称（去掉"市"）
             if city.ends_with("市") {
                 let short = city.trim_end_matches("市");
-                city_trie.insert(short, city.clone());
+                if alias_ok(short) {
+                    city_trie.insert(short, city.clone());
+                }
             }
         }
 
@@ -63,25 +187,272 @@ impl AddressParser {
             for suffix in &["区", "县", "市", "旗"] {
                 if district.ends_with(suffix) {
                     let short = district.trim_end_matches(suffix);
-                    if !short.is_empty() && short.chars().count() >= 2 {
+                    if alias_ok(short) {
                         district_trie.insert(short, district.clone());
                     }
                 }
             }
         }
 
+        // 港澳地名的繁体写法及常用别称（如"澳門"、"九龍城區"、"氹仔"）
+        for (sar, district, alias) in load_sar_aliases() {
+            match district {
+                None if options.short_names && index.provinces.contains(sar) => {
+                    province_trie.insert(alias, sar.to_string());
+                }
+                Some(district) if index.validate_district(sar, district) => {
+                    district_trie.insert(alias, district.to_string());
+                    if let Some(short) = alias.strip_suffix('區') {
+                        if alias_ok(short) {
+                            district_trie.insert(short, district.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // 构建乡镇街道前缀树（简称过于宽泛，易误伤道路名，只收录全称）
+        let mut town_trie = Trie::new();
+        for town in &index.towns {
+            town_trie.insert(town, town.clone());
+        }
+
+        // 构建村、社区前缀树（仅有自定义数据时非空）
+        let village_trie = {
+            let mut trie = Trie::new();
+            for village in &index.villages {
+                trie.insert(village, village.clone());
+                // 常见的村委会、居委会写法
+                if village.ends_with('村') {
+                    trie.insert(&format!("{}委会", village), village.clone());
+                    trie.insert(&format!("{}民委员会", village), village.clone());
+                } else if village.ends_with("社区") {
+                    trie.insert(&format!("{}居委会", village), village.clone());
+                    trie.insert(&format!("{}居民委员会", village), village.clone());
+                }
+            }
+            trie
+        };
+
+        // 构建历史名称前缀树
+        let mut history_city_trie = Trie::new();
+        let mut history_district_trie = Trie::new();
+        for redirect in &history {
+            match redirect.district {
+                None => {
+                    history_city_trie.insert(&redirect.city, redirect.city.clone());
+                    if let Some(short) = redirect.city.strip_suffix('市') {
+                        if alias_ok(short) {
+                            history_city_trie.insert(short, redirect.city.clone());
+                        }
+                    }
+                }
+                Some(ref district) => {
+                    history_district_trie.insert(district, district.clone());
+                    let short = district.trim_end_matches(&['区', '县', '市', '旗'][..]);
+                    if alias_ok(short) {
+                        history_district_trie.insert(short, district.clone());
+                    }
+                }
+            }
+        }
+
         Self {
             province_trie,
             city_trie,
             district_trie,
+            town_trie,
+            village_trie,
+            scoped_aliases: HashMap::new(),
+            history_city_trie,
+            history_district_trie,
+            history,
+            pinyin_tries: None,
+            fuzzy: None,
+            preprocess: PreprocessOptions::default(),
+            prefer_district: options.prefer_district,
+            english_names: OnceCell::new(),
             index,
             province_aliases: aliases,
+            centroids: load_centroids(),
+            postal_info: load_postal_info(),
         }
     }
 
-    /// 获取全局解析器实例
+    /// 创建支持拼音输入的解析器
+    ///
+    /// 额外构建省、市、区县的全拼与首字母前缀树，以字母开头的地址按拼音解析。
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::with_pinyin();
+    /// let result = parser.parse("guangdong shenzhen nanshan");
+    /// assert_eq!(result.district, Some("南山区".to_string()));
+    ///
+    /// let result = parser.parse("gd sz");
+    /// assert_eq!(result.city, Some("深圳市".to_string()));
+    /// ```
+    pub fn with_pinyin() -> Self {
+        let mut parser = Self::new();
+        parser.set_options(true, None, PreprocessOptions::default());
+        parser
+    }
+
+    /// 创建容错的解析器：精确匹配失败时按编辑距离（及同音字）纠正地名
+    ///
+    /// 纠错须符合层级关系：城市、区县须属于已识别的上级，
+    /// 没有上级时须紧跟一个属于它的下级地名。纠正的层级在
+    /// `provenance` 中记为 [`MatchSource::Fuzzy`]，可据此人工复核。
+    ///
+    /// ```rust
+    /// use cpca::{AddressParser, FuzzyOptions, MatchSource};
+    ///
+    /// let parser = AddressParser::with_fuzzy(FuzzyOptions::default());
+    /// let result = parser.parse("深川市南山区");
+    /// assert_eq!(result.city, Some("深圳市".to_string()));
+    /// assert_eq!(result.provenance.city, Some(MatchSource::Fuzzy));
+    /// assert!(result.is_fuzzy());
+    /// ```
+    pub fn with_fuzzy(options: FuzzyOptions) -> Self {
+        let mut parser = Self::new();
+        parser.fuzzy = Some(options);
+        parser
+    }
+
+    /// 创建使用指定预处理选项的解析器（默认全部开启）
+    ///
+    /// ```rust
+    /// use cpca::{AddressParser, PreprocessOptions};
+    ///
+    /// let result = AddressParser::new().parse("广东省 深圳市，南山区 科技园１号");
+    /// assert_eq!(result.district, Some("南山区".to_string()));
+    /// assert_eq!(result.detail, "科技园1号");
+    ///
+    /// let parser = AddressParser::with_preprocess(PreprocessOptions::none());
+    /// assert_eq!(parser.parse("广东省，深圳市").city, None);
+    /// ```
+    pub fn with_preprocess(options: PreprocessOptions) -> Self {
+        let mut parser = Self::new();
+        parser.preprocess = options;
+        parser
+    }
+
+    /// 设置拼音、纠错及预处理选项
+    pub(crate) fn set_options(
+        &mut self,
+        pinyin: bool,
+        fuzzy: Option<FuzzyOptions>,
+        preprocess: PreprocessOptions,
+    ) {
+        self.pinyin_tries =
+            pinyin.then(|| PinyinTries::build(&self.index, &self.province_aliases, &self.history));
+        self.fuzzy = fuzzy;
+        self.preprocess = preprocess;
+    }
+
+    /// 并入自定义别称，目标地名不存在或不属于所限定的上级时返回 [`ParseError::DataLoadError`]
+    ///
+    /// 全局别称直接插入对应层级的前缀树，与内置简称同名时以自定义的为准。
+    pub(crate) fn insert_aliases(&mut self, aliases: &Aliases) -> Result<(), ParseError> {
+        for alias in aliases.iter() {
+            if !self.alias_target_exists(alias) {
+                return Err(ParseError::DataLoadError(format!(
+                    "unknown alias target: {:?}",
+                    alias
+                )));
+            }
+            let trie = match (&alias.parent, alias.level) {
+                (Some(parent), level) => self
+                    .scoped_aliases
+                    .entry((level, parent.clone()))
+                    .or_default(),
+                (None, RegionLevel::Province) => &mut self.province_trie,
+                (None, RegionLevel::City) => &mut self.city_trie,
+                (None, RegionLevel::District) => &mut self.district_trie,
+            };
+            trie.insert(&alias.alias, alias.name.clone());
+        }
+        Ok(())
+    }
+
+    /// 别称的目标地名是否存在于该层级，并属于所限定的上级（城市的上级为省份，区县的上级为城市或省份）
+    fn alias_target_exists(&self, alias: &Alias) -> bool {
+        let parent = alias.parent.as_deref();
+        match alias.level {
+            RegionLevel::Province => parent.is_none() && self.index.provinces.contains(&alias.name),
+            // 反向索引中也有简称，须另外确认是全称
+            RegionLevel::City => {
+                self.index.cities.contains(&alias.name)
+                    && parent.is_none_or(|parent| {
+                        self.index
+                            .city_to_province
+                            .get(&alias.name)
+                            .is_some_and(|p| p == parent)
+                    })
+            }
+            RegionLevel::District => {
+                self.index.districts.contains(&alias.name)
+                    && parent.is_none_or(|parent| {
+                        self.index
+                            .district_to_city
+                            .get(&alias.name)
+                            .into_iter()
+                            .flatten()
+                            .any(|(p, c)| p == parent || c == parent)
+                    })
+            }
+        }
+    }
+
+    /// 在限定于 `parent` 之下的自定义别称中匹配开头最长的一个
+    fn scoped_prefix<'a>(
+        &self,
+        level: RegionLevel,
+        parent: Option<&str>,
+        text: &'a str,
+    ) -> Option<(&'a str, &String, usize)> {
+        if self.scoped_aliases.is_empty() {
+            return None;
+        }
+        self.scoped_aliases
+            .get(&(level, parent?.to_string()))?
+            .find_longest_prefix(text)
+    }
+
+    /// 获取用内置数据构建的初始全局实例
+    ///
+    /// 该实例不随 [`reload_global`](crate::reload_global) 更新，需要当前全局实例时请用
+    /// [`current`](Self::current)。
+    #[deprecated(note = "不随 reload_global 更新，请改用 AddressParser::current")]
     pub fn global() -> &'static AddressParser {
-        &GLOBAL_PARSER
+        &DEFAULT_PARSER
+    }
+
+    /// 获取当前的全局解析器实例
+    ///
+    /// 返回调用时的实例；之后 [`reload_global`](crate::reload_global) 替换全局实例时，
+    /// 已取得的实例不受影响，用完即释放。
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::current();
+    /// assert_eq!(parser.parse("深圳南山").district, Some("南山区".to_string()));
+    /// ```
+    pub fn current() -> Arc<AddressParser> {
+        GLOBAL_PARSER.load_full()
+    }
+
+    /// 以当前全局实例执行 `f`，读取无锁且不增加引用计数
+    pub(crate) fn with_global<R>(f: impl FnOnce(&AddressParser) -> R) -> R {
+        f(&GLOBAL_PARSER.load())
+    }
+
+    /// 替换全局实例，正在使用旧实例的调用不受影响
+    pub(crate) fn set_global(parser: AddressParser) {
+        GLOBAL_PARSER.store(Arc::new(parser));
     }
 
     /// 解析地址字符串
@@ -101,43 +472,696 @@ impl AddressParser {
     /// assert_eq!(result.province, Some("广东省".to_string()));
     /// ```
     pub fn parse(&self, address: &str) -> ParsedAddress {
-        let address = address.trim();
-        if address.is_empty() {
+        let input = self.preprocess.apply(address);
+        let text = input.as_str();
+        let mut result = match self.pinyin_tries {
+            Some(ref tries)
+                if text
+                    .trim_start()
+                    .starts_with(|c: char| c.is_ascii_alphabetic()) =>
+            {
+                self.parse_pinyin(tries, text)
+            }
+            _ => self.parse_hanzi(text),
+        };
+        result.spans.locate(&input, address);
+        result
+    }
+
+    /// 解析地址，地名互相矛盾或什么都没有识别出时返回错误
+    ///
+    /// 与 [`parse`](Self::parse) 结果相同，另外检查：没有识别出省、市、区县时为
+    /// [`ParseError::NotFound`]；写明的城市、区县不属于前面的省份、城市时为
+    /// [`ParseError::CityNotInProvince`] 等。缺少层级（如同名区县无法确定城市）不算错误。
+    ///
+    /// ```rust
+    /// use cpca::{AddressParser, ParseError};
+    ///
+    /// let parser = AddressParser::new();
+    /// assert!(parser.try_parse("广东省深圳市南山区").is_ok());
+    /// assert_eq!(
+    ///     parser.try_parse("广东省杭州市西湖区"),
+    ///     Err(ParseError::CityNotInProvince {
+    ///         province: "广东省".to_string(),
+    ///         city: "杭州市".to_string(),
+    ///     })
+    /// );
+    /// assert!(matches!(parser.try_parse("你好"), Err(ParseError::NotFound(_))));
+    /// ```
+    pub fn try_parse(&self, address: &str) -> Result<ParsedAddress, ParseError> {
+        let result = self.parse(address);
+        if result.province.is_none() && result.city.is_none() && result.district.is_none() {
+            return Err(ParseError::NotFound(address.to_string()));
+        }
+        self.check_consistency(&result)?;
+        Ok(result)
+    }
+
+    /// 严格解析：在 [`try_parse`](Self::try_parse) 的基础上要求省、市、区县都已确定
+    ///
+    /// 同名区县无法确定城市时为 [`ParseError::AmbiguousDistrict`]（附全部候选），
+    /// 缺少城市或区县（不设区的市除外）时为 [`ParseError::Incomplete`]。
+    ///
+    /// ```rust
+    /// use cpca::{AddressParser, ParseError};
+    ///
+    /// let parser = AddressParser::new();
+    /// assert!(parser.parse_strict("东莞市长安镇").is_ok());
+    /// match parser.parse_strict("朝阳区望京") {
+    ///     Err(ParseError::AmbiguousDistrict { candidates, .. }) => assert_eq!(candidates.len(), 2),
+    ///     other => panic!("{:?}", other),
+    /// }
+    /// ```
+    pub fn parse_strict(&self, address: &str) -> Result<ParsedAddress, ParseError> {
+        let result = self.try_parse(address)?;
+        let incomplete = |missing| ParseError::Incomplete {
+            address: address.to_string(),
+            missing,
+        };
+        if result.city.is_none() {
+            if let Some(ref district) = result.district {
+                let candidates: Vec<Region> = self
+                    .index
+                    .district_to_city
+                    .get(district)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(p, _)| {
+                        result
+                            .province
+                            .as_ref()
+                            .is_none_or(|province| province == p)
+                    })
+                    .filter_map(|(_, c)| self.index.find_region(c, Some(district)).cloned())
+                    .collect();
+                if candidates.len() > 1 {
+                    return Err(ParseError::AmbiguousDistrict {
+                        district: district.clone(),
+                        candidates,
+                    });
+                }
+            }
+            return Err(incomplete(RegionLevel::City));
+        }
+        if result.district.is_none() && !result.city_kind.is_some_and(|k| k.has_no_districts()) {
+            return Err(incomplete(RegionLevel::District));
+        }
+        Ok(result)
+    }
+
+    /// 检查解析出的地名之间、以及与详细地址开头写明的地名之间是否矛盾
+    ///
+    /// 详细地址开头只检查全称，如"广东省杭州市"中的"杭州市"，避免把"中山路"当作中山市。
+    fn check_consistency(&self, result: &ParsedAddress) -> Result<(), ParseError> {
+        let index = &self.index;
+        let in_province = |p: &String, d: &String| {
+            index
+                .district_to_city
+                .get(d)
+                .is_some_and(|cities| cities.iter().any(|(province, _)| province == p))
+        };
+        if let (Some(p), Some(c)) = (&result.province, &result.city) {
+            if index.city_to_province.get(c).is_some_and(|cp| cp != p) {
+                return Err(ParseError::CityNotInProvince {
+                    province: p.clone(),
+                    city: c.clone(),
+                });
+            }
+        }
+        if let (Some(p), None, Some(d)) = (&result.province, &result.city, &result.district) {
+            if !in_province(p, d) {
+                return Err(ParseError::DistrictNotInProvince {
+                    province: p.clone(),
+                    district: d.clone(),
+                });
+            }
+        }
+
+        let folded = to_jianti(&result.detail);
+        let rest = folded.trim_start_matches(is_separator);
+        let full_name = |trie: &Trie<String>| {
+            trie.find_longest_prefix(rest)
+                .filter(|(matched, name, _)| matched == name)
+                .map(|(_, name, _)| name.clone())
+        };
+        match (&result.province, &result.city, &result.district) {
+            (Some(p), None, None) => {
+                if let Some(city) = full_name(&self.city_trie) {
+                    if index.city_to_province.get(&city) != Some(p) {
+                        return Err(ParseError::CityNotInProvince {
+                            province: p.clone(),
+                            city,
+                        });
+                    }
+                }
+                if let Some(district) = full_name(&self.district_trie) {
+                    if !in_province(p, &district) {
+                        return Err(ParseError::DistrictNotInProvince {
+                            province: p.clone(),
+                            district,
+                        });
+                    }
+                }
+            }
+            (_, Some(c), None) => {
+                if let Some(district) = full_name(&self.district_trie) {
+                    if !index.validate_district(c, &district) {
+                        return Err(ParseError::DistrictNotInCity {
+                            city: c.clone(),
+                            district,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// 列出地址的候选解读，按置信度由高到低排列，最多返回 `limit` 个
+    ///
+    /// 同名区县（如北京、长春都有朝阳区）时 `parse` 不会填写城市，
+    /// 此方法则由区县反查出每个可能的（省份, 城市, 区县），详细地址中的乡镇街道
+    /// 位于其中某个候选时该候选置信度更高。只有一种解读时返回 `parse` 的结果。
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// let candidates = parser.parse_candidates("朝阳区望京", 5);
+    /// assert_eq!(candidates.len(), 2);
+    /// assert_eq!(candidates[0].address.city, Some("北京市".to_string()));
+    /// assert_eq!(candidates[1].address.city, Some("长春市".to_string()));
+    /// assert_eq!(candidates[0].score, 0.5);
+    /// ```
+    pub fn parse_candidates(&self, address: &str, limit: usize) -> Vec<Candidate> {
+        let input = self.preprocess.apply(address);
+        let text = input.as_str();
+        let folded = to_jianti(text);
+        let (interpretations, name) = self.interpretations(&folded);
+        if interpretations.len() <= 1 {
+            let mut candidates = vec![Candidate {
+                address: self.parse(address),
+                score: 1.0,
+                reason: CandidateReason::Unique,
+            }];
+            candidates.truncate(limit);
+            return candidates;
+        }
+
+        let count = interpretations.len();
+        let mut candidates: Vec<Candidate> = interpretations
+            .into_iter()
+            .map(|mut result| {
+                self.match_town(&mut result);
+                self.match_village(&mut result);
+                self.fill_codes(&mut result);
+                restore_original(&mut result, text, &folded);
+                result.spans.locate(&input, address);
+                let (score, reason) = match result.town {
+                    Some(ref town) => (4.0, CandidateReason::TownMatched { town: town.clone() }),
+                    None => (
+                        1.0,
+                        CandidateReason::SharedName {
+                            name: name.to_string(),
+                            count,
+                        },
+                    ),
+                };
+                Candidate {
+                    address: result,
+                    score,
+                    reason,
+                }
+            })
+            .collect();
+
+        let total: f64 = candidates.iter().map(|c| c.score).sum();
+        for candidate in &mut candidates {
+            candidate.score /= total;
+        }
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates.truncate(limit);
+        candidates
+    }
+
+    /// 从任意文本（聊天记录、OCR 结果等）中找出全部地址，按出现顺序返回
+    ///
+    /// 从每个出现省、市、区县名称的位置尝试解析，写出至少两级地名（如"深圳市南山区"、"深圳南山"）
+    /// 才视为地址。详细地址到换行、句末标点、逗号、顿号或电话号码为止，也不会越过下一个省、市全称
+    /// 或下一个地址；两个地址之间的"和"、"与"、"及"不计入详细地址。
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// let text = "收件人张三 13800138000 广东省深圳市南山区科技园";
+    /// let found = parser.extract(text);
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].address.district, Some("南山区".to_string()));
+    /// assert_eq!(&text[found[0].span.bytes.clone()], "广东省深圳市南山区科技园");
+    /// ```
+    pub fn extract(&self, text: &str) -> Vec<ExtractedAddress> {
+        let input = self.preprocess.apply(text);
+        let processed = input.as_str();
+        let folded = to_jianti(processed);
+
+        // 各级地名在文本中出现的位置，及该处是否为省、市全称
+        let mut hits: BTreeMap<usize, bool> = BTreeMap::new();
+        let tries = [&self.province_trie, &self.city_trie, &self.district_trie];
+        for (level, trie) in tries.into_iter().enumerate() {
+            for (pos, matched, name) in trie.find_all(&folded) {
+                *hits.entry(pos).or_default() |= level < 2 && matched == name;
+            }
+        }
+
+        let mut found = Vec::new();
+        let mut end = 0;
+        for &start in hits.keys() {
+            if start < end {
+                continue;
+            }
+            let Some(mut result) = self.address_at(processed, start) else {
+                continue;
+            };
+            self.cut_detail(&mut result, processed, &folded, start, &hits);
+            result.spans.shift(start);
+            let Some(extent) = result.spans.extent() else {
+                continue;
+            };
+            end = extent.bytes.end;
+            result.spans.locate(&input, text);
+            if let Some(span) = result.spans.extent() {
+                found.push(ExtractedAddress {
+                    address: result,
+                    span,
+                });
+            }
+        }
+        found
+    }
+
+    /// 解析从 `start` 开始到句末的文本，写出至少两级地名时返回结果（位置相对于 `start`）
+    fn address_at(&self, processed: &str, start: usize) -> Option<ParsedAddress> {
+        let len = processed[start..]
+            .find(is_sentence_end)
+            .unwrap_or(processed.len() - start);
+        let result = self.parse_hanzi(&processed[start..start + len]);
+        let spans = &result.spans;
+        let named = [&spans.province, &spans.city, &spans.district, &spans.town];
+        (named.iter().filter(|span| span.is_some()).count() >= 2).then_some(result)
+    }
+
+    /// 截断从文本中取出的详细地址：到逗号等分隔符、电话号码、下一个省、市全称或下一个地址为止
+    ///
+    /// 只在 `hits` 记录的地名位置上检查后续地址，不逐字查找。
+    fn cut_detail(
+        &self,
+        result: &mut ParsedAddress,
+        processed: &str,
+        folded: &str,
+        start: usize,
+        hits: &BTreeMap<usize, bool>,
+    ) {
+        let Some(ref span) = result.spans.detail else {
+            return;
+        };
+        let base = start + span.bytes.start;
+        let detail = &folded[base..start + span.bytes.end];
+        let punct = detail
+            .char_indices()
+            .find_map(|(pos, ch)| {
+                let rest = &detail[pos..];
+                let stop = matches!(ch, ',' | '，' | '、' | ';' | '；')
+                    || (ch.is_ascii_digit()
+                        && !detail[..pos].ends_with(|c: char| c.is_ascii_digit())
+                        && rest.chars().take_while(char::is_ascii_digit).count() >= 7);
+                stop.then_some(pos)
+            })
+            .unwrap_or(detail.len());
+        let next = hits
+            .range(base..base + punct)
+            .find(|(&pos, &full)| full || self.address_at(processed, pos).is_some())
+            .map(|(&pos, _)| pos - base);
+        let cut = next.unwrap_or(punct);
+        if cut == detail.len() {
+            return;
+        }
+        let mut kept = result.detail[..cut].trim_end();
+        if next.is_some() {
+            kept = kept.trim_end_matches(['和', '与', '及']).trim_end();
+        }
+        let kept = kept.len();
+        result.detail.truncate(kept);
+        result.spans.detail =
+            (kept > 0).then(|| Span::new(span.bytes.start..span.bytes.start + kept));
+    }
+
+    /// 拆分快递单式的收件信息：姓名、电话、邮编和地址，各部分的顺序和分隔符不限
+    ///
+    /// 支持"收件人："、"电话："等标签；没有标签时，取第一个不是地名的 2~4 字汉字片段作为姓名。
+    ///
+    /// ```rust
+    /// use cpca::{AddressParser, PhoneKind};
+    ///
+    /// let parser = AddressParser::new();
+    /// let r = parser.parse_recipient("张三，13800138000，广东省深圳市南山区科技园路1号");
+    /// assert_eq!(r.name, Some("张三".to_string()));
+    /// assert_eq!(r.phones[0].number, "13800138000");
+    /// assert_eq!(r.phones[0].kind, PhoneKind::Mobile);
+    /// assert_eq!(r.address.district, Some("南山区".to_string()));
+    /// assert_eq!(r.address.detail, "科技园路1号");
+    /// ```
+    pub fn parse_recipient(&self, text: &str) -> Recipient {
+        let input = self.preprocess.apply(text);
+        let processed = input.as_str();
+
+        // 电话、邮编
+        let mut phones = Vec::new();
+        let mut postal_code = None;
+        let mut masked = Vec::new();
+        for (range, number) in find_numbers(processed) {
+            match number {
+                Number::Phone(phone) => phones.push(phone),
+                Number::PostalCode(_) if postal_code.is_some() => continue,
+                Number::PostalCode(code) => postal_code = Some(code),
+            }
+            masked.push(range);
+        }
+
+        // 标签及姓名
+        let cleaned = mask(processed, &masked);
+        let segments = split_segments(&cleaned);
+        let mut name = None;
+        let mut expect_name = false;
+        for segment in &segments {
+            let text = &cleaned[segment.clone()];
+            if let Some((label, rest)) = strip_label(text) {
+                let start = segment.end - rest.len();
+                masked.push(segment.start..start);
+                expect_name = label == Label::Name && rest.is_empty();
+                if label == Label::Name && !rest.is_empty() && name.is_none() {
+                    name = Some(start..segment.end);
+                }
+            } else if std::mem::take(&mut expect_name) && name.is_none() {
+                name = Some(segment.clone());
+            }
+        }
+        let name = name
+            .or_else(|| {
+                segments
+                    .iter()
+                    .find(|s| {
+                        is_name_like(&cleaned[(*s).clone()])
+                            && !self.is_place(&cleaned[(*s).clone()])
+                    })
+                    .cloned()
+            })
+            .or_else(|| {
+                // 姓名紧贴在地址前，如"张三广东省深圳市……"
+                segments.iter().find_map(|s| {
+                    let text = &cleaned[s.clone()];
+                    let start = self.extract(text).first()?.span.bytes.start;
+                    (start > 0 && is_name_like(&text[..start])).then(|| s.start..s.start + start)
+                })
+            });
+        masked.extend(name.clone());
+
+        // 地址从第一个含地名的片段开始
+        let cleaned = mask(processed, &masked);
+        let start = split_segments(&cleaned)
+            .into_iter()
+            .find(|s| self.is_place(&cleaned[s.clone()]))
+            .map_or(0, |s| s.start);
+        let mut address = self.parse_hanzi(&cleaned[start..]);
+        // 去掉电话、姓名之前残留的分隔符
+        let kept = address.detail.trim_end_matches(is_separator).len();
+        address.detail.truncate(kept);
+        if let Some(ref mut span) = address.spans.detail {
+            span.bytes.end = span.bytes.start + kept;
+        }
+        address.spans.detail = address.spans.detail.take().filter(|_| kept > 0);
+        address.spans.shift(start);
+        address.spans.locate(&input, text);
+
+        Recipient {
+            name: name.map(|range| processed[range].to_string()),
+            phones,
+            postal_code,
+            address,
+        }
+    }
+
+    /// 文本开头是否为省、市、区县名称
+    fn is_place(&self, text: &str) -> bool {
+        let result = self.match_regions(&to_jianti(text));
+        result.province.is_some() || result.city.is_some() || result.district.is_some()
+    }
+
+    /// 列出地址开头地名的全部解读（城市或同名区县），只保留匹配最长的
+    ///
+    /// 返回（各解读的省市区、来源及位置, 地名）。
+    fn interpretations<'a>(&self, address: &'a str) -> (Vec<ParsedAddress>, &'a str) {
+        let text = address.trim_start();
+        let offset = address.len() - text.len();
+        let (province, start) = match self.province_trie.find_longest_prefix(text) {
+            Some((matched, province, len)) => (Some((province, source_of(matched, province))), len),
+            None => (None, 0),
+        };
+        let rest = &text[start..];
+        let province_ok = |p: &String| province.is_none_or(|(province, _)| province == p);
+        // 省份之后的地名依次占据 [start, start + city_len + district_len)
+        let build = |p: &String, c: &String, city: Option<(MatchSource, usize)>, district| {
+            let mut result = ParsedAddress {
+                province: Some(p.clone()),
+                city: Some(c.clone()),
+                ..Default::default()
+            };
+            result.provenance.province =
+                Some(province.map_or(MatchSource::InferredFromChild, |(_, source)| source));
+            result.provenance.city = Some(match (province, city) {
+                (Some((p, _)), _) if self.index.is_province_level_city(p) => {
+                    MatchSource::InferredFromParent
+                }
+                (_, Some((source, _))) => source,
+                _ => MatchSource::InferredFromChild,
+            });
+            if province.is_some() {
+                result.spans.province = Some(Span::new(offset..offset + start));
+            }
+            let mut pos = offset + start;
+            if let Some((_, len)) = city {
+                result.spans.city = Some(Span::new(pos..pos + len));
+                pos += len;
+            }
+            if let Some((district, source, len)) = district {
+                result.district = Some(district);
+                result.provenance.district = Some(source);
+                result.spans.district = Some(Span::new(pos..pos + len));
+                pos += len;
+            }
+            set_detail(&mut result, address, &address[pos..]);
+            (result, pos)
+        };
+        let mut found: Vec<(ParsedAddress, usize)> = Vec::new();
+
+        // 按城市解读，并在其下匹配区县
+        if let Some((matched, city, len)) = self.city_trie.find_longest_prefix(rest) {
+            if let Some(p) = self.index.city_to_province.get(city) {
+                if province_ok(p) {
+                    let district = self
+                        .district_trie
+                        .find_longest_prefix(&rest[len..])
+                        .and_then(|(d_matched, normalized, d_len)| {
+                            self.resolve_district(city, d_matched, normalized)
+                                .map(|district| {
+                                    let source = source_of(d_matched, &district);
+                                    (district, source, d_len)
+                                })
+                        });
+                    found.push(build(
+                        p,
+                        city,
+                        Some((source_of(matched, city), len)),
+                        district,
+                    ));
+                }
+            }
+        }
+
+        // 按区县解读，简称与全称对应的城市都列出
+        if let Some((matched, normalized, len)) = self.district_trie.find_longest_prefix(rest) {
+            let mut cities: Vec<&(String, String)> = [matched, normalized.as_str()]
+                .into_iter()
+                .filter_map(|key| self.index.district_to_city.get(key))
+                .flatten()
+                .filter(|(p, _)| province_ok(p))
+                .collect();
+            cities.sort();
+            cities.dedup();
+            for (p, c) in cities {
+                if let Some(district) = self.resolve_district(c, matched, normalized) {
+                    let source = source_of(matched, &district);
+                    found.push(build(p, c, None, Some((district, source, len))));
+                }
+            }
+        }
+
+        let end = found.iter().map(|(_, end)| *end).max().unwrap_or(0);
+        let key = |r: &ParsedAddress| (r.province.clone(), r.city.clone(), r.district.clone());
+        let mut interpretations: Vec<ParsedAddress> = found
+            .into_iter()
+            .filter(|(_, e)| *e == end)
+            .map(|(result, _)| result)
+            .collect();
+        interpretations.sort_by_key(key);
+        interpretations.dedup_by_key(|r| key(r));
+        // 同一城市既有市级又有区县级解读时（如"朝阳"），保留区县级
+        let specific: Vec<RegionCandidate> = interpretations
+            .iter()
+            .filter(|r| r.district.is_some())
+            .map(|r| (r.province.clone().unwrap_or_default(), r.city.clone(), None))
+            .collect();
+        interpretations.retain(|r| {
+            r.district.is_some()
+                || !specific
+                    .iter()
+                    .any(|(p, c, _)| r.province.as_ref() == Some(p) && r.city == *c)
+        });
+        let name = if end > offset + start {
+            &address[offset + start..end]
+        } else {
+            ""
+        };
+        (interpretations, name)
+    }
+
+    /// 解析汉字地址
+    ///
+    /// 繁体字先折叠为简体再匹配，详细地址保留原文。
+    fn parse_hanzi(&self, address: &str) -> ParsedAddress {
+        let folded = to_jianti(address);
+        let mut result = self.match_regions(&folded);
+        self.match_historical(&mut result);
+        self.match_town(&mut result);
+        self.match_village(&mut result);
+        self.fill_codes(&mut result);
+        restore_original(&mut result, address, &folded);
+        result
+    }
+
+    /// 匹配省、市、区名称
+    fn match_regions(&self, address: &str) -> ParsedAddress {
+        if address.trim().is_empty() {
             return ParsedAddress::empty();
         }
 
         let mut result = ParsedAddress::default();
-        let mut remaining = address.to_string();
+        // 剩余部分始终是原始输入的后缀，据此计算各部分的位置
+        let mut remaining = self.skip_separators(address.trim_start());
+        let span = |rest: &str, len: usize| {
+            let start = address.len() - rest.len();
+            Some(Span::new(start..start + len))
+        };
 
-        // 第一步：尝试匹配省份
-        if let Some((_matched, normalized, len)) =
-            self.province_trie.find_longest_prefix(&remaining)
-        {
+        // 第一步：尝试匹配省份（开头是城市、区县时不纠错，纠错后须紧跟该省的下级地名）
+        let province_match = self
+            .province_trie
+            .find_longest_prefix(remaining)
+            .or_else(|| {
+                let exact = self.city_trie.find_longest_prefix(remaining).is_some()
+                    || self.district_trie.find_longest_prefix(remaining).is_some();
+                self.fuzzy_find(&self.province_trie, remaining, |p, rest| {
+                    !exact && self.starts_with_child(p, None, rest)
+                })
+            });
+        if let Some((matched, normalized, len)) = province_match {
             result.province = Some(normalized.clone());
-            remaining = remaining[len..].to_string();
+            result.provenance.province =
+                Some(self.source_in(&self.province_trie, matched, normalized));
+            result.spans.province = span(remaining, len);
+            remaining = self.skip_separators(&remaining[len..]);
 
-            // 直辖市特殊处理：省=市，直接跳到区县匹配
-            if self.index.is_municipality(normalized) {
+            // 直辖市、特别行政区特殊处理：省=市，直接跳到区县匹配
+            if self.index.is_province_level_city(normalized) {
                 result.city = Some(normalized.clone());
-                // 直接尝试匹配区县
-                if let Some((_m, dist_normalized, dist_len)) =
-                    self.district_trie.find_longest_prefix(&remaining)
-                {
-                    // 验证区县是否属于该直辖市
-                    if self.index.validate_district(normalized, dist_normalized) {
-                        result.district = Some(dist_normalized.clone());
-                        remaining = remaining[dist_len..].to_string();
+                result.provenance.city = Some(MatchSource::InferredFromParent);
+                // 港澳地址的区县前常有片区名（如"九龙"、"新界"），跳过后再匹配
+                let areas = SAR_AREAS
+                    .iter()
+                    .filter(|area| remaining.starts_with(*area))
+                    .map(|area| area.len());
+                for skip in iter::once(0).chain(areas) {
+                    let rest = self.skip_separators(&remaining[skip..]);
+                    // 验证区县是否属于该直辖市或特别行政区
+                    let resolved = self
+                        .scoped_prefix(RegionLevel::District, Some(normalized), rest)
+                        .or_else(|| self.district_trie.find_longest_prefix(rest))
+                        .and_then(|(dist_matched, dist_normalized, dist_len)| {
+                            self.resolve_district(normalized, dist_matched, dist_normalized)
+                                .map(|district| (source_of(dist_matched, &district), district))
+                                .map(|(source, district)| (district, source, dist_len))
+                        });
+                    if let Some((district, source, len)) = resolved {
+                        result.district = Some(district);
+                        result.provenance.district = Some(source);
+                        result.spans.district = span(rest, len);
+                        remaining = self.skip_separators(&rest[len..]);
+                        break;
+                    }
+                }
+                if result.district.is_none() {
+                    let fuzzy = self.fuzzy_find(&self.district_trie, remaining, |d, _| {
+                        self.resolve_district(normalized, d, d).is_some()
+                    });
+                    if let Some((_, district, len)) = fuzzy {
+                        result.district = self.resolve_district(normalized, district, district);
+                        result.provenance.district = Some(MatchSource::Fuzzy);
+                        result.spans.district = span(remaining, len);
+                        remaining = self.skip_separators(&remaining[len..]);
                     }
                 }
-                result.detail = remaining.trim().to_string();
+                set_detail(&mut result, address, remaining);
                 return result;
             }
         }
 
+        // 城市之前直接写不设区的市下属的乡镇街道（如"广东省长安镇"）
+        if let Some(len) = self.match_orphan_town(&mut result, remaining) {
+            result.spans.town = span(remaining, len);
+            remaining = self.skip_separators(&remaining[len..]);
+            set_detail(&mut result, address, remaining);
+            return result;
+        }
+
         // 第二步：尝试匹配城市（但要先检查是否应该优先匹配区县）
         // 关键改进：当没有省份上下文时，如果输入看起来像区县（如"朝阳区"），应该优先匹配区县
-        let city_match = self.city_trie.find_longest_prefix(&remaining);
-        let district_match = self.district_trie.find_longest_prefix(&remaining);
+        let mut city_match = self
+            .scoped_prefix(RegionLevel::City, result.province.as_deref(), remaining)
+            .or_else(|| self.city_trie.find_longest_prefix(remaining));
+        let district_match = self.district_trie.find_longest_prefix(remaining);
+        let current_len = [&city_match, &district_match]
+            .iter()
+            .filter_map(|m| m.map(|(_, _, len)| len))
+            .max()
+            .unwrap_or(0);
+        if self.prefers_history(remaining, current_len, true) {
+            set_detail(&mut result, address, remaining);
+            return result;
+        }
+        if city_match.is_none() && district_match.is_none() {
+            // 纠错后的城市须属于已识别的省份，或紧跟一个属于它的区县
+            city_match = self.fuzzy_find(&self.city_trie, remaining, |c, rest| {
+                match result.province {
+                    Some(ref p) => self.index.city_to_province.get(c) == Some(p),
+                    None => self
+                        .index
+                        .city_to_province
+                        .get(c)
+                        .is_some_and(|p| self.starts_with_child(p, Some(c), rest)),
+                }
+            });
+        }
 
         // 判断是否应该优先使用区县匹配
         let prefer_district = if result.province.is_none() {
@@ -146,9 +1170,10 @@ impl AddressParser {
                 (Some((_, _, city_len)), Some((_, dist_normalized, dist_len))) => {
                     // 如果区县匹配更长，或者区县是完整形式（带后缀），优先使用区县
                     *dist_len > *city_len
-                        || dist_normalized.ends_with('区')
-                        || dist_normalized.ends_with('县')
-                        || dist_normalized.ends_with('旗')
+                        || (self.prefer_district
+                            && (dist_normalized.ends_with('区')
+                                || dist_normalized.ends_with('县')
+                                || dist_normalized.ends_with('旗')))
                 }
                 (Some(_), None) => false,
                 (None, Some(_)) => true,
@@ -160,8 +1185,9 @@ impl AddressParser {
 
         if prefer_district {
             // 优先处理区县匹配
-            if let Some((_matched, dist_normalized, dist_len)) = district_match {
+            if let Some((matched, dist_normalized, dist_len)) = district_match {
                 result.district = Some(dist_normalized.clone());
+                result.provenance.district = Some(source_of(matched, dist_normalized));
 
                 // 尝试反向查找城市和省份
                 if let Some(cities) = self.index.district_to_city.get(dist_normalized) {
@@ -169,15 +1195,18 @@ impl AddressParser {
                         // 唯一匹配
                         result.province = Some(cities[0].0.clone());
                         result.city = Some(cities[0].1.clone());
+                        result.provenance.province = Some(MatchSource::InferredFromChild);
+                        result.provenance.city = Some(MatchSource::InferredFromChild);
                     }
                     // 如果有多个匹配，不做假设，让用户提供更多上下文
                 }
 
-                remaining = remaining[dist_len..].to_string();
+                result.spans.district = span(remaining, dist_len);
+                remaining = self.skip_separators(&remaining[dist_len..]);
             }
         } else {
             // 正常流程：先匹配城市
-            if let Some((_matched, normalized, len)) = city_match {
+            if let Some((matched, normalized, len)) = city_match {
                 // 如果已有省份，验证城市是否属于该省
                 let valid_city = if let Some(ref province) = result.province {
                     self.index
@@ -191,38 +1220,72 @@ impl AddressParser {
 
                 if valid_city {
                     result.city = Some(normalized.clone());
+                    result.provenance.city =
+                        Some(self.source_in(&self.city_trie, matched, normalized));
 
                     // 如果之前没匹配到省份，尝试反向查找
                     if result.province.is_none() {
                         if let Some(province) = self.index.city_to_province.get(normalized) {
                             result.province = Some(province.clone());
+                            result.provenance.province = Some(MatchSource::InferredFromChild);
                         }
                     }
 
-                    remaining = remaining[len..].to_string();
+                    result.spans.city = span(remaining, len);
+                    remaining = self.skip_separators(&remaining[len..]);
                 }
             }
         }
 
         // 第三步：尝试匹配区县（如果还没匹配到）
-        if result.district.is_none() {
-            if let Some((_matched, normalized, len)) =
-                self.district_trie.find_longest_prefix(&remaining)
-            {
+        let exact_len = self
+            .district_trie
+            .find_longest_prefix(remaining)
+            .map_or(0, |(_, _, len)| len);
+        if result.district.is_none() && !self.prefers_history(remaining, exact_len, false) {
+            // 纠错后的区县须属于已识别的城市或省份
+            let district_match = self
+                .scoped_prefix(RegionLevel::District, result.city.as_deref(), remaining)
+                .or_else(|| {
+                    self.scoped_prefix(RegionLevel::District, result.province.as_deref(), remaining)
+                })
+                .or_else(|| self.district_trie.find_longest_prefix(remaining))
+                .or_else(|| {
+                    self.fuzzy_find(&self.district_trie, remaining, |d, _| {
+                        match (&result.city, &result.province) {
+                            (Some(c), _) => self.resolve_district(c, d, d).is_some(),
+                            (None, Some(p)) => self
+                                .index
+                                .district_to_city
+                                .get(d)
+                                .is_some_and(|cities| cities.iter().any(|(dp, _)| dp == p)),
+                            (None, None) => false,
+                        }
+                    })
+                });
+            if let Some((matched, normalized, len)) = district_match {
                 // 验证区县是否合法
-                let valid = if let Some(ref city) = result.city {
-                    self.index.validate_district(city, normalized)
-                        || self.validate_district_flexible(city, normalized)
+                let resolved = if let Some(ref city) = result.city {
+                    self.resolve_district(city, matched, normalized)
+                } else if let Some(ref province) = result.province {
+                    // 写明了省份时，区县须属于该省，否则留在详细地址中
+                    self.index
+                        .district_to_city
+                        .get(normalized)
+                        .is_some_and(|cities| cities.iter().any(|(p, _)| p == province))
+                        .then(|| normalized.clone())
                 } else {
-                    true // 没有城市信息时，先接受
+                    Some(normalized.clone()) // 没有城市信息时，先接受
                 };
 
-                if valid {
+                if let Some(normalized) = resolved {
+                    result.provenance.district =
+                        Some(self.source_in(&self.district_trie, matched, &normalized));
                     result.district = Some(normalized.clone());
 
                     // 如果之前没匹配到城市，尝试反向查找
                     if result.city.is_none() {
-                        if let Some(cities) = self.index.district_to_city.get(normalized) {
+                        if let Some(cities) = self.index.district_to_city.get(&normalized) {
                             if cities.len() == 1 {
                                 // 唯一匹配
                                 result.province = Some(cities[0].0.clone());
@@ -234,6 +1297,9 @@ impl AddressParser {
                                     result.city = Some(city.clone());
                                 }
                             }
+                            if result.city.is_some() {
+                                result.provenance.city = Some(MatchSource::InferredFromChild);
+                            }
                         }
                     }
 
@@ -242,26 +1308,706 @@ impl AddressParser {
                         if let Some(ref city) = result.city {
                             if let Some(province) = self.index.city_to_province.get(city) {
                                 result.province = Some(province.clone());
+                                result.provenance.province = Some(MatchSource::InferredFromChild);
                             }
                         }
                     }
 
-                    remaining = remaining[len..].to_string();
+                    result.spans.district = span(remaining, len);
+                    remaining = self.skip_separators(&remaining[len..]);
+                }
+            }
+        }
+
+        // 处理直辖市、特别行政区的特殊情况：省=市
+        if let Some(ref province) = result.province {
+            if self.index.is_province_level_city(province) && result.city.is_none() {
+                result.city = Some(province.clone());
+                result.provenance.city = Some(MatchSource::InferredFromParent);
+            }
+        }
+
+        // 剩余部分作为详细地址
+        set_detail(&mut result, address, remaining);
+
+        result
+    }
+
+    /// 启用预处理时跳过地名之间的空白和分隔符
+    fn skip_separators<'t>(&self, text: &'t str) -> &'t str {
+        if self.preprocess.separators {
+            text.trim_start_matches(is_separator)
+        } else {
+            text
+        }
+    }
+
+    /// 从详细地址开头取走 `len` 字节，其后的分隔符一并跳过
+    fn take_detail(&self, result: &mut ParsedAddress, len: usize) -> Option<Span> {
+        take_detail(result, len, self.preprocess.separators)
+    }
+
+    /// 模糊查找地名，未启用模糊匹配时返回 `None`
+    fn fuzzy_find<'a, 't>(
+        &'a self,
+        trie: &'a Trie<String>,
+        text: &'t str,
+        accept: impl Fn(&String, &str) -> bool,
+    ) -> Option<(&'t str, &'a String, usize)> {
+        self.fuzzy.as_ref()?.find(trie, text, accept)
+    }
+
+    /// 文本开头是否为该省（及城市）下属的城市或区县
+    fn starts_with_child(&self, province: &str, city: Option<&str>, text: &str) -> bool {
+        let city_ok = city.is_none()
+            && self
+                .city_trie
+                .find_longest_prefix(text)
+                .is_some_and(|(_, c, _)| {
+                    self.index
+                        .city_to_province
+                        .get(c)
+                        .is_some_and(|p| p == province)
+                });
+        city_ok
+            || self.district_trie.find_longest_prefix(text).is_some_and(
+                |(matched, d, _)| match city {
+                    Some(c) => self.resolve_district(c, matched, d).is_some(),
+                    None => self
+                        .index
+                        .district_to_city
+                        .get(d)
+                        .is_some_and(|cities| cities.iter().any(|(p, _)| p == province)),
+                },
+            )
+    }
+
+    /// 文本开头的已撤销或更名的地名是否比现行地名匹配得更长
+    ///
+    /// 如"莱芜市"之于"莱芜区"的简称、"无为县"之于"无为市"的简称，是则留给
+    /// `match_historical` 换算。`cities` 为真时同时匹配原地级市，
+    /// 原地级市全称与现行区县同名时（如"巢湖市"）也按原地级市换算。
+    fn prefers_history(&self, text: &str, current_len: usize, cities: bool) -> bool {
+        let city = cities
+            && self
+                .history_city_trie
+                .find_longest_prefix(text)
+                .is_some_and(|(matched, city, len)| {
+                    len > current_len || (len == current_len && matched == city)
+                });
+        city || self
+            .history_district_trie
+            .find_longest_prefix(text)
+            .is_some_and(|(_, _, len)| len > current_len)
+    }
+
+    /// 匹配文本不在前缀树中的为模糊匹配，否则按全称、简称区分
+    fn source_in(&self, trie: &Trie<String>, matched: &str, normalized: &str) -> MatchSource {
+        let exact = trie.get(matched).is_some()
+            || self
+                .scoped_aliases
+                .values()
+                .any(|t| t.get(matched).is_some());
+        if self.fuzzy.is_some() && !exact {
+            MatchSource::Fuzzy
+        } else {
+            source_of(matched, normalized)
+        }
+    }
+
+    /// 在详细地址开头匹配已撤销或更名的城市、区县，换算为现行名称
+    ///
+    /// 只处理 `match_regions` 留下的部分：现行名称匹配得不比历史名称短时按现行名称，
+    /// 见 [`prefers_history`](Self::prefers_history)。
+    /// 原地级市下属区县写在县级市之后时（如"巢湖市和县"），按原地级市匹配其下属区县。
+    fn match_historical(&self, result: &mut ParsedAddress) {
+        // 城市级：如"襄樊市"
+        if result.city.is_none() {
+            if let Some((_matched, city, len)) =
+                self.history_city_trie.find_longest_prefix(&result.detail)
+            {
+                let redirect = self.history.iter().find(|r| {
+                    r.district.is_none()
+                        && r.city == *city
+                        && result.province.as_ref().is_none_or(|p| *p == r.province)
+                });
+                if let Some(redirect) = redirect {
+                    result.spans.city = self.take_detail(result, len);
+                    self.apply_redirect(result, redirect);
+                    self.match_redirected_district(result, redirect);
+                }
+            }
+        }
+
+        // 区县级：如"崇文区"，或原地级市下属的区县
+        let Some((_matched, district, len)) = self
+            .history_district_trie
+            .find_longest_prefix(&result.detail)
+        else {
+            return;
+        };
+        let candidates: Vec<&Redirect> = self
+            .history
+            .iter()
+            .filter(|r| r.district.as_ref() == Some(district))
+            .filter(|r| result.province.as_ref().is_none_or(|p| *p == r.province))
+            .filter(|r| match (&result.city, &result.district) {
+                (_, Some(d)) => *d == r.city,
+                (Some(c), None) => *c == r.city || *c == r.new_city,
+                (None, None) => true,
+            })
+            .collect();
+        if let Some(redirect) = pick_redirect(candidates, result.city.as_slice()) {
+            // 原地级市已换算为同名县级市时，以区县级换算为准
+            result.historical.retain(|h| h.old_name != redirect.city);
+            let span = self.take_detail(result, len);
+            if result.district.as_ref() == Some(&redirect.city) {
+                // 先被识别为区县的其实是原地级市
+                result.spans.city = result.spans.district.take();
+            }
+            self.apply_redirect(result, redirect);
+            result.spans.district = span.filter(|_| result.district.is_some());
+        }
+    }
+
+    /// 城市换算后，在新城市下匹配现行区县（原下属区县名称未变的情况）
+    fn match_redirected_district(&self, result: &mut ParsedAddress, redirect: &Redirect) {
+        let Some((matched, normalized, len)) =
+            self.district_trie.find_longest_prefix(&result.detail)
+        else {
+            return;
+        };
+        if self.prefers_history(&result.detail, len, false) {
+            // 下属区县也已更名（如"乌兰察布盟集宁市"），由区县级记录换算
+            return;
+        }
+        let Some(district) = self.resolve_district(&redirect.new_city, matched, normalized) else {
+            return;
+        };
+        if let Some(last) = result.historical.last_mut() {
+            last.old_name = format!("{}{}", redirect.city, district);
+            last.new_name = format!("{}{}", redirect.new_city, district);
+        }
+        result.provenance.district = Some(source_of(matched, &district));
+        result.district = Some(district);
+        result.spans.district = self.take_detail(result, len);
+    }
+
+    /// 按调整记录改写为现行地区，并记录换算
+    fn apply_redirect(&self, result: &mut ParsedAddress, redirect: &Redirect) {
+        let provenance = &mut result.provenance;
+        if result.province.as_ref() != Some(&redirect.province) {
+            provenance.province = Some(MatchSource::InferredFromChild);
+        }
+        if redirect.district.is_none() {
+            provenance.city = Some(MatchSource::Historical);
+        } else if result.city.is_none() {
+            provenance.city = Some(MatchSource::InferredFromChild);
+        } else if result.city.as_ref() != Some(&redirect.new_city) {
+            provenance.city = Some(MatchSource::Historical);
+        }
+        provenance.district = redirect
+            .new_district
+            .as_ref()
+            .map(|_| MatchSource::Historical);
+        result.province = Some(redirect.province.clone());
+        result.city = Some(redirect.new_city.clone());
+        result.district = redirect.new_district.clone();
+        result.historical.push(HistoricalName {
+            old_name: redirect.old_name(),
+            new_name: redirect.new_name(),
+            effective: redirect.effective.clone(),
+        });
+    }
+
+    /// 城市未知时，在文本开头匹配不设区的市直属的乡镇街道，返回匹配长度
+    ///
+    /// 乡镇须在已匹配的省份内唯一，没有省份时须在全国唯一；
+    /// 城市或区县的匹配不短于乡镇时不处理（如"东城区"仍按区县解析）。
+    fn match_orphan_town(&self, result: &mut ParsedAddress, text: &str) -> Option<usize> {
+        let (_matched, town, len) = self.town_trie.find_longest_prefix(text)?;
+        let longer = |trie: &Trie<String>| {
+            trie.find_longest_prefix(text)
+                .is_some_and(|(_, _, other)| other >= len)
+        };
+        if longer(&self.city_trie) || longer(&self.district_trie) {
+            return None;
+        }
+
+        let candidates: Vec<&Region> = self
+            .index
+            .town_to_regions
+            .get(town)?
+            .iter()
+            .filter(|r| r.district.is_none())
+            .filter(|r| result.province.as_ref().is_none_or(|p| *p == r.province))
+            .collect();
+        let [region] = candidates[..] else {
+            return None;
+        };
+
+        if result.province.is_none() {
+            result.province = Some(region.province.clone());
+            result.provenance.province = Some(MatchSource::InferredFromChild);
+        }
+        result.city = Some(region.city.clone());
+        result.provenance.city = Some(MatchSource::InferredFromChild);
+        result.town = Some(town.clone());
+        result.provenance.town = Some(MatchSource::Explicit);
+        Some(len)
+    }
+
+    /// 在详细地址开头匹配乡镇街道
+    ///
+    /// 只有在城市已确定时才匹配，并校验乡镇属于已匹配的区县（或不设区的市）。
+    /// 区县缺失而乡镇在该市内唯一时，顺带补全区县。
+    fn match_town(&self, result: &mut ParsedAddress) {
+        let Some(ref city) = result.city else {
+            return;
+        };
+        if result.town.is_some() {
+            return;
+        }
+        let Some((_matched, town, len)) = self.town_trie.find_longest_prefix(&result.detail) else {
+            return;
+        };
+
+        let candidates = self.index.find_town(town, city, result.district.as_deref());
+        if candidates.len() != 1 {
+            return;
+        }
+
+        if result.district.is_none() {
+            result.district = candidates[0].district.clone();
+            if result.district.is_some() {
+                result.provenance.district = Some(MatchSource::InferredFromChild);
+            }
+        }
+        result.town = Some(town.clone());
+        result.provenance.town = Some(MatchSource::Explicit);
+        result.spans.town = self.take_detail(result, len);
+    }
+
+    /// 在详细地址开头匹配村、社区（仅在乡镇已确定时）
+    fn match_village(&self, result: &mut ParsedAddress) {
+        let (Some(ref city), Some(ref town)) = (&result.city, &result.town) else {
+            return;
+        };
+        let Some((_matched, village, len)) = self.village_trie.find_longest_prefix(&result.detail)
+        else {
+            return;
+        };
+
+        if self.index.find_village(village, city, town).len() == 1 {
+            result.village = Some(village.clone());
+            result.provenance.village = Some(MatchSource::Explicit);
+            result.spans.village = self.take_detail(result, len);
+        }
+    }
+
+    /// 解析拼音地址（全拼或首字母，分隔符可有可无）
+    ///
+    /// 拼音同音、首字母重复的情况很多，因此每一级都保留全部候选，
+    /// 在所有组合中选取层级一致、止于词边界且匹配最长的一组，
+    /// 只填写候选唯一的层级。
+    fn parse_pinyin(&self, tries: &PinyinTries, address: &str) -> ParsedAddress {
+        let input = PinyinInput::new(address);
+        let text = input.compact.as_str();
+        // 匹配必须止于词边界；匹配长度相同时先出现的组合优先，即高层级、长匹配优先
+        // (匹配长度, 候选组合, 所用调整记录, 城市候选名称, 各级匹配长度)
+        type Best<'a> = (
+            usize,
+            Vec<RegionCandidate>,
+            Vec<&'a Redirect>,
+            Option<&'a Vec<String>>,
+            [usize; 3],
+        );
+        let mut best: Option<Best> = None;
+        for (provinces, p_len) in pinyin_prefixes(&tries.province, text) {
+            for (cities, c_len) in pinyin_prefixes(&tries.city, &text[p_len..]) {
+                for (districts, d_len) in pinyin_prefixes(&tries.district, &text[p_len + c_len..]) {
+                    let len = p_len + c_len + d_len;
+                    if len == 0
+                        || !input.is_boundary(len)
+                        || best.as_ref().is_some_and(|(best_len, ..)| len <= *best_len)
+                    {
+                        continue;
+                    }
+                    let (candidates, redirects) =
+                        self.romanized_regions(provinces, cities, districts);
+                    if !candidates.is_empty() {
+                        best = Some((len, candidates, redirects, cities, [p_len, c_len, d_len]));
+                    }
+                }
+            }
+        }
+
+        let Some((len, candidates, redirects, cities, lens)) = best else {
+            return self.parse_hanzi(address);
+        };
+
+        let mut spans = [None, None, None];
+        let mut start = 0;
+        for (span, len) in spans.iter_mut().zip(lens) {
+            if len > 0 {
+                *span = Some(Span::new(input.original_span(start, start + len)));
+            }
+            start += len;
+        }
+        let rest = address[input.original_end(len)..]
+            .trim_start_matches(|c: char| !c.is_alphanumeric() && c.is_ascii());
+        let mut result = self.resolve_candidates(&candidates, spans);
+        self.record_redirect(&mut result, redirects, cities);
+        set_detail(&mut result, address, rest);
+        result
+    }
+
+    /// 解析英文地址（由小到大、逗号分隔）
+    ///
+    /// 从右向左依次识别省、市、区县，支持 "Province"、"City"、"District"、"County"
+    /// 等通名及官方英文译名，国家名和邮政编码会被忽略，其余部分作为详细地址。
+    ///
+    /// # 示例
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// let result =
+    ///     parser.parse_en("Room 101, 1 Keji Rd, Nanshan District, Shenzhen, Guangdong, China");
+    /// assert_eq!(result.province, Some("广东省".to_string()));
+    /// assert_eq!(result.city, Some("深圳市".to_string()));
+    /// assert_eq!(result.district, Some("南山区".to_string()));
+    /// assert_eq!(result.detail, "Room 101, 1 Keji Rd");
+    /// ```
+    pub fn parse_en(&self, address: &str) -> ParsedAddress {
+        let input = self.preprocess.apply(address);
+        let mut result = self.parse_en_text(input.as_str());
+        result.spans.locate(&input, address);
+        result
+    }
+
+    fn parse_en_text(&self, address: &str) -> ParsedAddress {
+        let names = self
+            .english_names
+            .get_or_init(|| EnglishNames::build(&self.index, &self.history));
+        let mut tokens: Vec<&str> = address
+            .split([',', '，', ';', '\n'])
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect();
+        while tokens
+            .last()
+            .is_some_and(|t| is_country(t) || strip_postal_code(t).is_empty())
+        {
+            tokens.pop();
+        }
+
+        let mut provinces: Option<Vec<String>> = None;
+        let mut cities: Option<Vec<String>> = None;
+        let mut districts: Option<Vec<String>> = None;
+        let mut spans = [None, None, None];
+        while let Some(token) = tokens.last() {
+            let token = strip_postal_code(token);
+            // 港澳片区名称位于特别行政区与区县之间，跳过
+            if districts.is_none()
+                && is_sar_area(token)
+                && provinces
+                    .iter()
+                    .flatten()
+                    .any(|p| self.index.is_province_level_city(p))
+            {
+                tokens.pop();
+                continue;
+            }
+            let levels: &[RegionLevel] = if districts.is_some() {
+                &[]
+            } else if cities.is_some() {
+                &[RegionLevel::District]
+            } else if provinces.is_some() {
+                &[RegionLevel::City, RegionLevel::District]
+            } else {
+                &[
+                    RegionLevel::Province,
+                    RegionLevel::City,
+                    RegionLevel::District,
+                ]
+            };
+
+            let matched = levels.iter().find_map(|&level| {
+                let names = Some(names.lookup(level, token)).filter(|n| !n.is_empty())?;
+                let (mut p, mut c, mut d) = (provinces.clone(), cities.clone(), districts.clone());
+                match level {
+                    RegionLevel::Province => p = Some(names),
+                    RegionLevel::City => c = Some(names),
+                    RegionLevel::District => d = Some(names),
+                }
+                let consistent = !self
+                    .romanized_regions(p.as_ref(), c.as_ref(), d.as_ref())
+                    .0
+                    .is_empty();
+                consistent.then_some((level, p, c, d))
+            });
+            let Some((level, p, c, d)) = matched else {
+                break;
+            };
+            let start = offset_in(address, token);
+            spans[level as usize] = Some(Span::new(start..start + token.len()));
+            (provinces, cities, districts) = (p, c, d);
+            tokens.pop();
+        }
+
+        if provinces.is_none() && cities.is_none() && districts.is_none() {
+            let mut result = ParsedAddress::default();
+            set_detail(&mut result, address, address);
+            return result;
+        }
+        let (candidates, redirects) =
+            self.romanized_regions(provinces.as_ref(), cities.as_ref(), districts.as_ref());
+        let mut result = self.resolve_candidates(&candidates, spans);
+        self.record_redirect(&mut result, redirects, cities.as_ref());
+        result.detail = tokens.join(", ");
+        // 详细地址为剩余各段的整体范围
+        if let (Some(first), Some(last)) = (tokens.first(), tokens.last()) {
+            let (start, end) = (
+                offset_in(address, first),
+                offset_in(address, last) + last.len(),
+            );
+            result.spans.detail = Some(Span::new(start..end));
+        }
+        result
+    }
+
+    /// 由候选组合生成解析结果，只填写候选唯一的层级
+    ///
+    /// `spans` 为省、市、区县各级地名在地址中的位置，没有的为反查所得。
+    /// 详细地址由调用方填写。
+    fn resolve_candidates(
+        &self,
+        candidates: &[RegionCandidate],
+        spans: [Option<Span>; 3],
+    ) -> ParsedAddress {
+        let mut result = ParsedAddress {
+            province: unique(candidates.iter().map(|c| &c.0)).cloned(),
+            city: unique(candidates.iter().map(|c| &c.1)).cloned().flatten(),
+            district: unique(candidates.iter().map(|c| &c.2)).cloned().flatten(),
+            ..Default::default()
+        };
+        let [province, city, district] = spans;
+        let source = |span: &Option<Span>| {
+            if span.is_some() {
+                MatchSource::Alias
+            } else {
+                MatchSource::InferredFromChild
+            }
+        };
+        let provenance = &mut result.provenance;
+        provenance.province = result.province.as_ref().map(|_| source(&province));
+        provenance.city = result.city.as_ref().map(|_| source(&city));
+        provenance.district = result.district.as_ref().map(|_| source(&district));
+        // 只记录已确定的层级
+        result.spans.province = province.filter(|_| result.province.is_some());
+        result.spans.city = city.filter(|_| result.city.is_some());
+        result.spans.district = district.filter(|_| result.district.is_some());
+        if let Some(ref province) = result.province {
+            if self.index.is_province_level_city(province) && result.city.is_none() {
+                result.city = Some(province.clone());
+                result.provenance.city = Some(MatchSource::InferredFromParent);
+            }
+        }
+        self.fill_codes(&mut result);
+        result
+    }
+
+    /// 拼音、英文地址的候选组合
+    ///
+    /// 现行名称优先；没有一致的现行组合时，按调整记录换算已撤销或更名的城市、区县，
+    /// 并返回所用的调整记录。城市换算后区县仍为现行名称的（如 "Xiangfan Fancheng"），
+    /// 在新城市下匹配。
+    fn romanized_regions(
+        &self,
+        provinces: Option<&Vec<String>>,
+        cities: Option<&Vec<String>>,
+        districts: Option<&Vec<String>>,
+    ) -> (Vec<RegionCandidate>, Vec<&Redirect>) {
+        let current = self.consistent_regions(provinces, cities, districts);
+        if !current.is_empty() {
+            return (current, Vec::new());
+        }
+
+        let province_ok = |p: &String| provinces.is_none_or(|ps| ps.contains(p));
+        let city_ok =
+            |r: &Redirect| cities.is_none_or(|cs| cs.contains(&r.city) || cs.contains(&r.new_city));
+        let mut candidates = Vec::new();
+        let mut redirects = Vec::new();
+        for redirect in self.history.iter().filter(|r| province_ok(&r.province)) {
+            let count = candidates.len();
+            let province = redirect.province.clone();
+            let new_city = Some(redirect.new_city.clone());
+            match (&redirect.district, districts) {
+                (Some(old), Some(ds)) if ds.contains(old) && city_ok(redirect) => {
+                    candidates.push((province, new_city, redirect.new_district.clone()));
+                }
+                (None, _) if cities.is_some_and(|cs| cs.contains(&redirect.city)) => {
+                    match districts {
+                        None => {
+                            candidates.push((province, new_city, redirect.new_district.clone()))
+                        }
+                        Some(ds) => candidates.extend(
+                            ds.iter()
+                                .filter(|d| self.index.validate_district(&redirect.new_city, d))
+                                .map(|d| (province.clone(), new_city.clone(), Some(d.clone()))),
+                        ),
+                    }
                 }
+                _ => {}
+            }
+            if candidates.len() > count {
+                redirects.push(redirect);
             }
         }
+        candidates.sort();
+        candidates.dedup();
+        (candidates, redirects)
+    }
+
+    /// 拼音、英文地址换算了已撤销或更名的地名时，记录换算并标注来源
+    ///
+    /// 只在解析结果的区县（或城市）已确定、且能确定所用的调整记录时记录。
+    fn record_redirect(
+        &self,
+        result: &mut ParsedAddress,
+        redirects: Vec<&Redirect>,
+        cities: Option<&Vec<String>>,
+    ) {
+        let applies = |r: &Redirect| {
+            result.city.as_ref() == Some(&r.new_city)
+                && (r.district.is_none() || result.district == r.new_district)
+        };
+        let mut redirects: Vec<&Redirect> = redirects.into_iter().filter(|r| applies(r)).collect();
+        // 区县级记录比城市级记录具体（如原巢湖市庐江县）
+        if redirects.iter().any(|r| r.district.is_some()) {
+            redirects.retain(|r| r.district.is_some());
+        }
+        let cities = cities.map_or(&[][..], |cs| cs.as_slice());
+        let Some(redirect) = pick_redirect(redirects, cities) else {
+            return;
+        };
+
+        let (old_name, new_name) = match (&redirect.district, &result.district) {
+            // 城市换算后区县仍为现行名称
+            (None, Some(d)) if result.district != redirect.new_district => (
+                format!("{}{}", redirect.city, d),
+                format!("{}{}", redirect.new_city, d),
+            ),
+            _ => (redirect.old_name(), redirect.new_name()),
+        };
+        let provenance = &mut result.provenance;
+        if redirect.district.is_none()
+            || result.spans.city.is_some() && redirect.city != redirect.new_city
+        {
+            provenance.city = Some(MatchSource::Historical);
+        }
+        if redirect.new_district.is_some() && result.district == redirect.new_district {
+            provenance.district = Some(MatchSource::Historical);
+        }
+        result.historical.push(HistoricalName {
+            old_name,
+            new_name,
+            effective: redirect.effective.clone(),
+        });
+    }
 
-        // 处理直辖市的特殊情况：省=市
-        if let Some(ref province) = result.province {
-            if self.index.is_municipality(province) && result.city.is_none() {
-                result.city = Some(province.clone());
-            }
+    /// 列出与各级候选名称一致的 (省份, 城市, 区县) 组合
+    fn consistent_regions(
+        &self,
+        provinces: Option<&Vec<String>>,
+        cities: Option<&Vec<String>>,
+        districts: Option<&Vec<String>>,
+    ) -> Vec<RegionCandidate> {
+        let province_ok = |p: &String| provinces.is_none_or(|ps| ps.contains(p));
+        let city_ok = |c: &String| cities.is_none_or(|cs| cs.contains(c));
+
+        if let Some(districts) = districts {
+            districts
+                .iter()
+                .flat_map(|d| {
+                    self.index
+                        .district_to_city
+                        .get(d)
+                        .into_iter()
+                        .flatten()
+                        .filter(|(p, c)| province_ok(p) && city_ok(c))
+                        .map(move |(p, c)| (p.clone(), Some(c.clone()), Some(d.clone())))
+                })
+                .collect()
+        } else if let Some(cities) = cities {
+            cities
+                .iter()
+                .filter_map(|c| {
+                    self.index
+                        .city_to_province
+                        .get(c)
+                        .filter(|p| province_ok(p))
+                        .map(|p| (p.clone(), Some(c.clone()), None))
+                })
+                .collect()
+        } else {
+            provinces
+                .into_iter()
+                .flatten()
+                .map(|p| (p.clone(), None, None))
+                .collect()
         }
+    }
 
-        // 剩余部分作为详细地址
-        result.detail = remaining.trim().to_string();
+    /// 根据已匹配的名称填充行政区划代码及城市类型
+    fn fill_codes(&self, result: &mut ParsedAddress) {
+        result.province_code = result
+            .province
+            .as_ref()
+            .and_then(|p| self.index.province_codes.get(p))
+            .cloned();
+        result.city_code = result
+            .city
+            .as_ref()
+            .and_then(|c| self.index.city_codes.get(c))
+            .cloned();
+        result.city_kind = result
+            .city
+            .as_ref()
+            .and_then(|c| self.index.city_kinds.get(c))
+            .copied();
+        result.district_code = match (&result.city, &result.district) {
+            (Some(c), Some(d)) => self
+                .index
+                .district_codes
+                .get(&(c.clone(), d.clone()))
+                .cloned(),
+            _ => None,
+        };
+    }
 
-        result
+    /// 在指定城市下确定区县全称
+    ///
+    /// 同一简称可能对应多个区县（如"朝阳"既是朝阳区也是朝阳县），
+    /// 前缀树只保留其中一个，因此需要按城市下的区县重新确定。
+    fn resolve_district(&self, city: &str, matched: &str, normalized: &str) -> Option<String> {
+        if self.index.validate_district(city, normalized) {
+            return Some(normalized.to_string());
+        }
+        if let Some(districts) = self.index.city_districts.get(city) {
+            if let Some(d) = districts
+                .iter()
+                .find(|d| d.trim_end_matches(&['区', '县', '市', '旗'][..]) == matched)
+            {
+                return Some(d.clone());
+            }
+        }
+        if self.validate_district_flexible(city, normalized) {
+            Some(normalized.to_string())
+        } else {
+            None
+        }
     }
 
     /// 灵活验证区县（处理简称情况）
@@ -379,6 +2125,128 @@ impl AddressParser {
         result.province.is_some() || result.city.is_some()
     }
 
+    /// 根据行政区划代码反查地区
+    ///
+    /// 支持市级和区县级代码，省级代码不对应单条记录，返回 `None`。
+    ///
+    /// # 示例
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// let region = parser.region_by_code("110105").unwrap();
+    /// assert_eq!(region.full_name(), "北京市北京市朝阳区");
+    /// ```
+    pub fn region_by_code(&self, code: &str) -> Option<&Region> {
+        self.index.find_region_by_code(code.trim())
+    }
+
+    /// 获取地区中心点坐标（WGS-84）
+    ///
+    /// 坐标取各级政府驻地的近似位置。`district` 为 `None` 时查城市，`city` 为空时查省份；
+    /// 所查一级没有坐标时返回 `None`，不退回上级。
+    ///
+    /// # 示例
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// let c = parser.region_centroid("广东省", "深圳市", Some("南山区")).unwrap();
+    /// let gcj = c.to_gcj02();
+    /// assert!(gcj.lng > c.lng);
+    /// ```
+    pub fn region_centroid(
+        &self,
+        province: &str,
+        city: &str,
+        district: Option<&str>,
+    ) -> Option<Coordinate> {
+        let key = (
+            province.to_string(),
+            city.to_string(),
+            district.unwrap_or_default().to_string(),
+        );
+        self.centroids.get(&key).copied()
+    }
+
+    /// 获取邮政编码
+    ///
+    /// 区县没有单独的邮政编码时，返回所属城市的邮政编码。
+    ///
+    /// # 示例
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// assert_eq!(parser.postal_code("广东省", "深圳市", Some("南山区")), Some("518000"));
+    /// ```
+    pub fn postal_code(&self, province: &str, city: &str, district: Option<&str>) -> Option<&str> {
+        self.lookup_postal_info(province, city, district, |info| info.postal_code.as_deref())
+    }
+
+    /// 获取电话区号
+    ///
+    /// 区县没有单独的区号时，返回所属城市的区号。
+    pub fn area_code(&self, province: &str, city: &str, district: Option<&str>) -> Option<&str> {
+        self.lookup_postal_info(province, city, district, |info| info.area_code.as_deref())
+    }
+
+    /// 按区县、城市的顺序查找邮政编码、电话区号
+    fn lookup_postal_info<'a>(
+        &'a self,
+        province: &str,
+        city: &str,
+        district: Option<&str>,
+        field: impl Fn(&'a PostalInfo) -> Option<&'a str>,
+    ) -> Option<&'a str> {
+        let key = |d: &str| (province.to_string(), city.to_string(), d.to_string());
+        district
+            .and_then(|d| self.postal_info.get(&key(d)))
+            .and_then(&field)
+            .or_else(|| self.postal_info.get(&key("")).and_then(&field))
+    }
+
+    /// 根据邮政编码反查地区
+    ///
+    /// 返回 [`postal_code`](Self::postal_code) 为该编码的全部城市和区县，
+    /// 包括沿用所属城市编码的区县。
+    pub fn regions_by_postal_code(&self, postal_code: &str) -> Vec<&Region> {
+        self.regions_by_postal_info(|info| info.postal_code.as_deref() == Some(postal_code.trim()))
+    }
+
+    /// 根据电话区号反查地区
+    ///
+    /// 返回 [`area_code`](Self::area_code) 为该区号的全部城市和区县。
+    pub fn regions_by_area_code(&self, area_code: &str) -> Vec<&Region> {
+        self.regions_by_postal_info(|info| info.area_code.as_deref() == Some(area_code.trim()))
+    }
+
+    fn regions_by_postal_info(&self, pred: impl Fn(&PostalInfo) -> bool) -> Vec<&Region> {
+        let matches = |r: &Region| {
+            let info = |d: &str| {
+                self.postal_info
+                    .get(&(r.province.clone(), r.city.clone(), d.to_string()))
+            };
+            let own = r.district.as_deref().and_then(info);
+            // 区县自身登记的字段优先，缺失的字段沿用城市
+            match (own, info("")) {
+                (Some(own), Some(city)) => pred(&PostalInfo {
+                    postal_code: own.postal_code.clone().or_else(|| city.postal_code.clone()),
+                    area_code: own.area_code.clone().or_else(|| city.area_code.clone()),
+                }),
+                (own, city) => own.or(city).is_some_and(&pred),
+            }
+        };
+        let mut regions: Vec<&Region> = self
+            .index
+            .city_district_regions
+            .values()
+            .filter(|r| matches(r))
+            .collect();
+        regions.sort_by_key(|r| r.full_name());
+        regions
+    }
+
     /// 获取所有省份列表
     pub fn provinces(&self) -> Vec<&String> {
         self.index.provinces.iter().collect()
@@ -415,6 +2283,33 @@ impl AddressParser {
             .map(|districts| districts.iter().collect())
             .unwrap_or_default()
     }
+
+    /// 行政区划树的全部省级节点，按行政区划代码排序，见 [`RegionNode`]
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let parser = AddressParser::new();
+    /// for province in parser.province_nodes() {
+    ///     for city in province.children() {
+    ///         let districts = city.children();
+    ///         println!("{} {} {}", province.name(), city.name(), districts.len());
+    ///     }
+    /// }
+    /// ```
+    pub fn province_nodes(&self) -> Vec<RegionNode<'_>> {
+        RegionNode::provinces(&self.index)
+    }
+
+    /// 按全称查找行政区划树中的节点，城市须属于该省，区县须属于该市
+    pub fn region_node(
+        &self,
+        province: &str,
+        city: Option<&str>,
+        district: Option<&str>,
+    ) -> Option<RegionNode<'_>> {
+        RegionNode::find(&self.index, province, city, district)
+    }
 }
 
 impl Default for AddressParser {
@@ -423,14 +2318,173 @@ impl Default for AddressParser {
     }
 }
 
+/// 拼音前缀树在文本开头的所有匹配（由长到短），最后是"不匹配"一项
+fn pinyin_prefixes<'a>(
+    trie: &'a Trie<Vec<String>>,
+    text: &str,
+) -> Vec<(Option<&'a Vec<String>>, usize)> {
+    trie.find_prefixes(text)
+        .into_iter()
+        .rev()
+        .map(|(_, names, len)| (Some(names), len))
+        .chain(iter::once((None, 0)))
+        .collect()
+}
+
+/// 匹配到全称时为 `Explicit`，否则为简称或别称
+fn source_of(matched: &str, normalized: &str) -> MatchSource {
+    if matched == normalized {
+        MatchSource::Explicit
+    } else {
+        MatchSource::Alias
+    }
+}
+
+/// `inner` 在 `outer` 中的字节位置（`inner` 须为 `outer` 的子串切片）
+fn offset_in(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+/// 以原始输入的后缀 `rest`（去除首尾空白）作为详细地址
+fn set_detail(result: &mut ParsedAddress, input: &str, rest: &str) {
+    let detail = rest.trim();
+    let start = input.len() - rest.trim_start().len();
+    result.detail = detail.to_string();
+    result.spans.detail = (!detail.is_empty()).then(|| Span::new(start..start + detail.len()));
+}
+
+/// 按位置从原始输入中取回详细地址（繁简折叠不改变字节位置），
+/// 写成繁体的全称记为别称
+fn restore_original(result: &mut ParsedAddress, input: &str, folded: &str) {
+    if input == folded {
+        return;
+    }
+    if let Some(ref span) = result.spans.detail {
+        result.detail = input[span.bytes.clone()].to_string();
+    }
+    let spans = &result.spans;
+    let provenance = &mut result.provenance;
+    let levels = [
+        (&spans.province, &mut provenance.province),
+        (&spans.city, &mut provenance.city),
+        (&spans.district, &mut provenance.district),
+        (&spans.town, &mut provenance.town),
+        (&spans.village, &mut provenance.village),
+    ];
+    for (span, source) in levels {
+        if let (Some(span), Some(MatchSource::Explicit)) = (span, &source) {
+            if input[span.bytes.clone()] != folded[span.bytes.clone()] {
+                *source = Some(MatchSource::Alias);
+            }
+        }
+    }
+}
+
+/// 从详细地址开头取走 `len` 字节，返回取走部分的位置
+fn take_detail(result: &mut ParsedAddress, len: usize, separators: bool) -> Option<Span> {
+    let rest = if separators {
+        result.detail[len..].trim_start_matches(is_separator)
+    } else {
+        result.detail[len..].trim_start()
+    };
+    let skipped = result.detail.len() - rest.len();
+    result.detail = rest.to_string();
+    let detail = result.spans.detail.take()?;
+    let start = detail.bytes.start;
+    if !result.detail.is_empty() {
+        result.spans.detail = Some(Span::new(start + skipped..detail.bytes.end));
+    }
+    Some(Span::new(start..start + len))
+}
+
+/// 以空白和分隔符切分文本，返回各非空片段的位置（间隔号属于姓名，不切分）
+fn split_segments(text: &str) -> Vec<Range<usize>> {
+    let mut segments = Vec::new();
+    let mut start = None;
+    for (pos, ch) in text.char_indices().chain(iter::once((text.len(), ' '))) {
+        match (is_separator(ch) && ch != '·', start) {
+            (true, Some(begin)) => {
+                segments.push(begin..pos);
+                start = None;
+            }
+            (false, None) => start = Some(pos),
+            _ => {}
+        }
+    }
+    segments
+}
+
+/// 文本中地址的边界：换行及句末标点
+fn is_sentence_end(ch: char) -> bool {
+    matches!(ch, '\n' | '\r' | '。' | '!' | '！' | '?' | '？')
+}
+
+/// 所有取值相同时返回该值
+fn unique<T: PartialEq>(mut values: impl Iterator<Item = T>) -> Option<T> {
+    let first = values.next()?;
+    values.all(|v| v == first).then_some(first)
+}
+
+/// 从适用的调整记录中选取一条，不能确定时返回 `None`
+///
+/// 原城市是所写城市之一的优先（如"芜湖市无为县"不按原巢湖市无为县换算）；
+/// 先后隶属不同城市、承继地区相同时，取最近一次调整。
+fn pick_redirect<'a>(mut candidates: Vec<&'a Redirect>, cities: &[String]) -> Option<&'a Redirect> {
+    if candidates.iter().any(|r| cities.contains(&r.city)) {
+        candidates.retain(|r| cities.contains(&r.city));
+    }
+    let same_successor = candidates.windows(2).all(|pair| {
+        (&pair[0].new_city, &pair[0].new_district) == (&pair[1].new_city, &pair[1].new_district)
+    });
+    if same_successor {
+        candidates.into_iter().max_by_key(|r| r.effective.as_str())
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::region::{CandidateReason, MatchSource, Provenance, RegionKind};
 
     fn parser() -> AddressParser {
         AddressParser::new()
     }
 
+    /// 深圳南山、福田两区的街道（内置数据不含，供测试乡镇相关功能）
+    const TOWNS: &str = "sheng,shi,qu,zhen\n\
+    广东省,深圳市,南山区,南头街道\n\
+    广东省,深圳市,南山区,南山街道\n\
+    广东省,深圳市,南山区,沙河街道\n\
+    广东省,深圳市,南山区,蛇口街道\n\
+    广东省,深圳市,南山区,招商街道\n\
+    广东省,深圳市,南山区,粤海街道\n\
+    广东省,深圳市,南山区,桃源街道\n\
+    广东省,深圳市,南山区,西丽街道\n\
+    广东省,深圳市,福田区,园岭街道\n\
+    广东省,深圳市,福田区,南园街道\n\
+    广东省,深圳市,福田区,福田街道\n\
+    广东省,深圳市,福田区,沙头街道\n\
+    广东省,深圳市,福田区,香蜜湖街道\n\
+    广东省,深圳市,福田区,梅林街道\n\
+    广东省,深圳市,福田区,莲花街道\n\
+    广东省,深圳市,福田区,华富街道\n\
+    广东省,深圳市,福田区,福保街道\n\
+    广东省,深圳市,福田区,华强北街道\n";
+
+    /// 村、社区样例
+    const VILLAGES: &str = "sheng,shi,qu,zhen,cun\n\
+    广东省,东莞市,,长安镇,乌沙社区\n\
+    广东省,深圳市,南山区,粤海街道,大冲社区\n";
+
+    fn town_parser() -> AddressParser {
+        AddressParser::builder()
+            .towns_from_reader(TOWNS.as_bytes())
+            .build()
+            .unwrap()
+    }
+
     // ==================== 基本功能测试 ====================
 
     #[test]
@@ -547,107 +2601,530 @@ mod tests {
         assert_eq!(r.detail, "望京");
     }
 
-    #[test]
-    fn test_parse_municipality_short() {
-        let p = parser();
-        let r = p.parse("北京朝阳区");
+    #[test]
+    fn test_parse_municipality_short() {
+        let p = parser();
+        let r = p.parse("北京朝阳区");
+
+        assert_eq!(r.province, Some("北京市".to_string()));
+        assert_eq!(r.city, Some("北京市".to_string()));
+        assert_eq!(r.district, Some("朝阳区".to_string()));
+    }
+
+    #[test]
+    fn test_parse_shanghai() {
+        let p = parser();
+        let r = p.parse("上海市浦东新区陆家嘴");
+
+        assert_eq!(r.province, Some("上海市".to_string()));
+        assert_eq!(r.city, Some("上海市".to_string()));
+        assert_eq!(r.district, Some("浦东新区".to_string()));
+    }
+
+    #[test]
+    fn test_parse_chongqing() {
+        let p = parser();
+        let r = p.parse("重庆市渝中区解放碑");
+
+        assert_eq!(r.province, Some("重庆市".to_string()));
+        assert_eq!(r.city, Some("重庆市".to_string()));
+        assert_eq!(r.district, Some("渝中区".to_string()));
+    }
+
+    // ==================== 自治区测试 ====================
+
+    #[test]
+    fn test_parse_autonomous_region() {
+        let p = parser();
+        let r = p.parse("广西壮族自治区南宁市青秀区");
+
+        assert_eq!(r.province, Some("广西壮族自治区".to_string()));
+        assert_eq!(r.city, Some("南宁市".to_string()));
+        assert_eq!(r.district, Some("青秀区".to_string()));
+    }
+
+    #[test]
+    fn test_parse_autonomous_region_short() {
+        let p = parser();
+        let r = p.parse("广西南宁市");
+
+        assert_eq!(r.province, Some("广西壮族自治区".to_string()));
+        assert_eq!(r.city, Some("南宁市".to_string()));
+    }
+
+    #[test]
+    fn test_parse_inner_mongolia() {
+        let p = parser();
+        let r = p.parse("内蒙古自治区呼和浩特市");
+
+        assert_eq!(r.province, Some("内蒙古自治区".to_string()));
+        assert_eq!(r.city, Some("呼和浩特市".to_string()));
+    }
+
+    #[test]
+    fn test_parse_inner_mongolia_short() {
+        let p = parser();
+        let r = p.parse("内蒙古呼和浩特");
+
+        assert_eq!(r.province, Some("内蒙古自治区".to_string()));
+        assert_eq!(r.city, Some("呼和浩特市".to_string()));
+    }
+
+    // ==================== 不设区的市测试 ====================
+
+    #[test]
+    fn test_parse_dongguan() {
+        // 东莞市没有区，直接是镇
+        let p = parser();
+        let r = p.parse("广东省东莞市长安镇");
+
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.city, Some("东莞市".to_string()));
+        // 不设区的市，下一级直接是镇
+        assert_eq!(r.district, None);
+        assert_eq!(r.town, Some("长安镇".to_string()));
+        assert_eq!(r.detail, "");
+    }
+
+    #[test]
+    fn test_parse_zhongshan() {
+        let p = parser();
+        let r = p.parse("广东省中山市小榄镇");
+
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.city, Some("中山市".to_string()));
+        assert_eq!(r.town, Some("小榄镇".to_string()));
+    }
+
+    #[test]
+    fn test_city_kind() {
+        let p = parser();
+
+        let r = p.parse("湖北省仙桃市沙嘴街道");
+        assert_eq!(r.city, Some("仙桃市".to_string()));
+        assert_eq!(r.city_kind, Some(RegionKind::ProvinceAdministered));
+        assert!(!r.city_kind.unwrap().is_prefecture_level());
+
+        let r = p.parse("新疆石河子市北一路");
+        assert_eq!(r.city_kind, Some(RegionKind::CorpsCity));
+
+        let r = p.parse("广东省东莞市长安镇");
+        assert_eq!(r.city_kind, Some(RegionKind::NoDistrictCity));
+        assert!(r.city_kind.unwrap().has_no_districts());
+
+        let r = p.parse("深圳南山");
+        assert_eq!(r.city_kind, Some(RegionKind::PrefectureCity));
+
+        assert_eq!(p.parse("科技园").city_kind, None);
+    }
+
+    // ==================== 乡镇街道测试 ====================
+
+    #[test]
+    fn test_parse_town() {
+        let p = town_parser();
+
+        let r = p.parse("广东省深圳市南山区粤海街道科技园");
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.town, Some("粤海街道".to_string()));
+        assert_eq!(r.detail, "科技园");
+
+        // 缺少区县时，根据唯一的乡镇补全区县
+        let r = p.parse("深圳市粤海街道科技园");
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.town, Some("粤海街道".to_string()));
+
+        // 乡镇与区县不匹配时不识别
+        let r = p.parse("深圳市福田区粤海街道");
+        assert_eq!(r.town, None);
+        assert_eq!(r.detail, "粤海街道");
+
+        // 没有城市时，按不设区的市直属的乡镇补全城市
+        let r = p.parse("长安镇某某路");
+        assert_eq!(r.city, Some("东莞市".to_string()));
+        assert_eq!(r.town, Some("长安镇".to_string()));
+        assert_eq!(r.detail, "某某路");
+
+        let r = p.parse("广东省长安镇");
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.city, Some("东莞市".to_string()));
+        assert_eq!(r.district, None);
+        assert_eq!(r.town, Some("长安镇".to_string()));
+        assert_eq!(r.detail, "");
+        assert_eq!(r.provenance.province, Some(MatchSource::Explicit));
+        assert_eq!(r.provenance.city, Some(MatchSource::InferredFromChild));
+
+        let r = p.parse("广东省东城街道幸福里");
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.city, Some("东莞市".to_string()));
+        assert_eq!(r.town, Some("东城街道".to_string()));
+        assert_eq!(r.detail, "幸福里");
+
+        let r = p.parse("海南省那大镇");
+        assert_eq!(r.city, Some("儋州市".to_string()));
+        assert_eq!(r.town, Some("那大镇".to_string()));
+
+        // 乡镇不属于写明的省份时不补全，区县全称仍按区县解析
+        assert_eq!(p.parse("浙江省长安镇").city, None);
+        assert_eq!(p.parse("东城区").city, Some("北京市".to_string()));
+    }
+
+    #[test]
+    fn test_parse_village() {
+        let p = AddressParser::builder()
+            .towns_from_reader(TOWNS.as_bytes())
+            .villages_from_reader(VILLAGES.as_bytes())
+            .build()
+            .unwrap();
+
+        let r = p.parse("广东省东莞市长安镇乌沙社区居委会旁");
+        assert_eq!(r.town, Some("长安镇".to_string()));
+        assert_eq!(r.village, Some("乌沙社区".to_string()));
+        assert_eq!(r.detail, "旁");
+
+        let r = p.parse("深圳市南山区粤海街道大冲社区大冲一路");
+        assert_eq!(r.village, Some("大冲社区".to_string()));
+        assert_eq!(r.detail, "大冲一路");
+
+        // 乡镇未知时不匹配
+        let r = p.parse("深圳市南山区大冲社区");
+        assert_eq!(r.village, None);
+
+        // 默认不含村、社区数据
+        let r = town_parser().parse("深圳市南山区粤海街道大冲社区");
+        assert_eq!(r.village, None);
+        assert_eq!(r.detail, "大冲社区");
+    }
+
+    // ==================== 自治州测试 ====================
+
+    #[test]
+    fn test_parse_autonomous_prefecture() {
+        let p = parser();
+        let r = p.parse("云南省大理白族自治州大理市");
+
+        assert_eq!(r.province, Some("云南省".to_string()));
+        assert_eq!(r.city, Some("大理白族自治州".to_string()));
+        assert_eq!(r.district, Some("大理市".to_string()));
+    }
+
+    // ==================== 来源与置信度测试 ====================
 
-        assert_eq!(r.province, Some("北京市".to_string()));
-        assert_eq!(r.city, Some("北京市".to_string()));
-        assert_eq!(r.district, Some("朝阳区".to_string()));
+    #[test]
+    fn test_provenance() {
+        use MatchSource::*;
+        let p = town_parser();
+
+        let r = p.parse("广东省深圳市南山区");
+        assert_eq!(r.provenance.province, Some(Explicit));
+        assert_eq!(r.provenance.city, Some(Explicit));
+        assert_eq!(r.provenance.district, Some(Explicit));
+        assert_eq!(r.confidence(), 1.0);
+
+        let r = p.parse("深圳南山");
+        assert_eq!(r.provenance.province, Some(InferredFromChild));
+        assert_eq!(r.provenance.city, Some(Alias));
+        assert_eq!(r.provenance.district, Some(Alias));
+
+        let r = p.parse("北京市朝阳区");
+        assert_eq!(r.provenance.city, Some(InferredFromParent));
+
+        let r = p.parse("北京市崇文区");
+        assert_eq!(r.provenance.district, Some(Historical));
+        let r = p.parse("湖北省襄樊市樊城区");
+        assert_eq!(r.provenance.city, Some(Historical));
+        assert_eq!(r.provenance.district, Some(Explicit));
+
+        // 由乡镇补全区县
+        let r = p.parse("深圳市粤海街道");
+        assert_eq!(r.provenance.district, Some(InferredFromChild));
+        assert_eq!(r.provenance.town, Some(Explicit));
+
+        let r = p.parse_en("Nanshan District, Shenzhen");
+        assert_eq!(r.provenance.province, Some(InferredFromChild));
+        assert_eq!(r.provenance.district, Some(Alias));
+
+        assert_eq!(p.parse("科技园").provenance, Provenance::default());
     }
 
     #[test]
-    fn test_parse_shanghai() {
+    fn test_confidence() {
         let p = parser();
-        let r = p.parse("上海市浦东新区陆家嘴");
-
-        assert_eq!(r.province, Some("上海市".to_string()));
-        assert_eq!(r.city, Some("上海市".to_string()));
-        assert_eq!(r.district, Some("浦东新区".to_string()));
+        let explicit = p.parse("广东省深圳市南山区").confidence();
+        let alias = p.parse("广东深圳南山").confidence();
+        let inferred = p.parse("深圳南山").confidence();
+        // 同名区县，城市无法确定
+        let ambiguous = p.parse("南山区").confidence();
+        assert!(explicit > alias && alias > inferred && inferred > ambiguous);
+
+        // 不设区的市没有区县不扣分
+        assert_eq!(p.parse("广东省东莞市长安镇").confidence(), 1.0);
+        assert!(p.parse("广东省深圳市").confidence() < 1.0);
+        assert_eq!(p.parse("科技园").confidence(), 0.0);
     }
 
     #[test]
-    fn test_parse_chongqing() {
-        let p = parser();
-        let r = p.parse("重庆市渝中区解放碑");
+    fn test_parse_fuzzy() {
+        let p = AddressParser::with_fuzzy(FuzzyOptions::default());
 
-        assert_eq!(r.province, Some("重庆市".to_string()));
-        assert_eq!(r.city, Some("重庆市".to_string()));
-        assert_eq!(r.district, Some("渝中区".to_string()));
-    }
+        // 形近字：由下级区县确认
+        let r = p.parse("深川市南山区科技园");
+        assert_eq!(r.city, Some("深圳市".to_string()));
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.provenance.city, Some(MatchSource::Fuzzy));
+        assert_eq!(r.provenance.district, Some(MatchSource::Explicit));
+        assert_eq!(r.detail, "科技园");
+        assert!(r.is_fuzzy());
+        assert!(r.confidence() < p.parse("深圳市南山区").confidence());
+
+        // 由上级确认
+        let r = p.parse("广东省深圳市南三区");
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.spans.district.unwrap().chars, 6..9);
+        let r = p.parse("广冬省深圳市");
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.provenance.province, Some(MatchSource::Fuzzy));
+
+        // 同音字
+        let r = p.parse("浙江杭洲");
+        assert_eq!(r.city, Some("杭州市".to_string()));
+        let strict = AddressParser::with_fuzzy(FuzzyOptions {
+            homophones: false,
+            ..Default::default()
+        });
+        assert_eq!(strict.parse("浙江杭洲").city, None);
 
-    // ==================== 自治区测试 ====================
+        // 无法由层级确认的不纠正
+        let r = p.parse("深川市");
+        assert_eq!(r.city, None);
+        assert_eq!(r.detail, "深川市");
+        assert_eq!(p.parse("南三区").district, None);
+
+        // 精确匹配不受影响，默认不纠错
+        let r = p.parse("广东省深圳市南山区");
+        assert!(!r.is_fuzzy());
+        assert!(!parser().parse("深川市南山区").is_fuzzy());
+        assert_eq!(parser().parse("浙江杭洲").city, None);
+    }
 
     #[test]
-    fn test_parse_autonomous_region() {
-        let p = parser();
-        let r = p.parse("广西壮族自治区南宁市青秀区");
-
-        assert_eq!(r.province, Some("广西壮族自治区".to_string()));
-        assert_eq!(r.city, Some("南宁市".to_string()));
-        assert_eq!(r.district, Some("青秀区".to_string()));
+    fn test_parse_traditional() {
+        let p = town_parser();
+        let input = "廣東省深圳市南山區粵海街道科技園路1號";
+        let r = p.parse(input);
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.town, Some("粤海街道".to_string()));
+        // 详细地址保留原文，繁体全称记为别称
+        assert_eq!(r.detail, "科技園路1號");
+        assert_eq!(r.provenance.province, Some(MatchSource::Alias));
+        assert_eq!(r.provenance.city, Some(MatchSource::Explicit));
+        assert_eq!(&input[r.spans.town.clone().unwrap().bytes], "粵海街道");
+
+        let r = p.parse("遼寧省瀋陽市和平區");
+        assert_eq!(r.city, Some("沈阳市".to_string()));
+        assert_eq!(r.district, Some("和平区".to_string()));
+        let r = p.parse("臺北市大安區");
+        assert_eq!(r.province, Some("台湾省".to_string()));
+
+        // 输出繁体地名
+        let r = p.parse("內蒙古呼和浩特市").to_traditional();
+        assert_eq!(r.province, Some("內蒙古自治區".to_string()));
+        let r = p.parse("香港灣仔區告士打道").to_traditional();
+        assert_eq!(r.full_address(), "香港特別行政區灣仔區告士打道");
+
+        let candidates = p.parse_candidates("朝陽區望京", 5);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].address.detail, "望京");
     }
 
     #[test]
-    fn test_parse_autonomous_region_short() {
-        let p = parser();
-        let r = p.parse("广西南宁市");
-
-        assert_eq!(r.province, Some("广西壮族自治区".to_string()));
-        assert_eq!(r.city, Some("南宁市".to_string()));
+    fn test_spans() {
+        let p = town_parser();
+        let input = "  广东省深圳市南山区粤海街道 科技园 ";
+        let r = p.parse(input);
+        let spans = &r.spans;
+        assert_eq!(spans.province.as_ref().unwrap().bytes, 2..11);
+        assert_eq!(spans.province.as_ref().unwrap().chars, 2..5);
+        assert_eq!(spans.city.as_ref().unwrap().chars, 5..8);
+        assert_eq!(spans.district.as_ref().unwrap().chars, 8..11);
+        assert_eq!(spans.town.as_ref().unwrap().chars, 11..15);
+        let detail = spans.detail.as_ref().unwrap();
+        assert_eq!(&input[detail.bytes.clone()], "科技园");
+        assert_eq!(detail.chars, 16..19);
+
+        // 推断得到的层级没有位置
+        let r = p.parse("深圳南山");
+        assert_eq!(r.spans.province, None);
+        assert_eq!(r.spans.city.unwrap().bytes, 0..6);
+        assert_eq!(r.spans.district.unwrap().bytes, 6..12);
+        assert_eq!(r.spans.detail, None);
+
+        // 跳过的片区名、历史名称
+        let input = "香港九龙九龙城区太子道西";
+        let r = p.parse(input);
+        assert_eq!(&input[r.spans.district.unwrap().bytes], "九龙城区");
+        let input = "北京市崇文区天坛路";
+        let r = p.parse(input);
+        assert_eq!(&input[r.spans.district.unwrap().bytes], "崇文区");
+        assert_eq!(&input[r.spans.detail.unwrap().bytes], "天坛路");
+
+        // 拼音、英文地址
+        let input = " guangdong shenzhen nanshan keji road";
+        let r = AddressParser::with_pinyin().parse(input);
+        assert_eq!(&input[r.spans.province.unwrap().bytes], "guangdong");
+        assert_eq!(&input[r.spans.district.unwrap().bytes], "nanshan");
+        assert_eq!(r.spans.detail.unwrap().chars, 28..37);
+        let input = "Room 101, 1 Keji Rd, Nanshan District, Shenzhen, Guangdong, China";
+        let r = p.parse_en(input);
+        assert_eq!(&input[r.spans.city.unwrap().bytes], "Shenzhen");
+        assert_eq!(&input[r.spans.district.unwrap().bytes], "Nanshan District");
+        assert_eq!(&input[r.spans.detail.unwrap().bytes], "Room 101, 1 Keji Rd");
     }
 
+    // ==================== 候选解读测试 ====================
+
     #[test]
-    fn test_parse_inner_mongolia() {
+    fn test_parse_candidates() {
         let p = parser();
-        let r = p.parse("内蒙古自治区呼和浩特市");
 
-        assert_eq!(r.province, Some("内蒙古自治区".to_string()));
-        assert_eq!(r.city, Some("呼和浩特市".to_string()));
+        let candidates = p.parse_candidates("朝阳区望京", 10);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].address.city, Some("北京市".to_string()));
+        assert_eq!(candidates[1].address.city, Some("长春市".to_string()));
+        assert_eq!(candidates[0].address.detail, "望京");
+        assert_eq!(
+            candidates[1].address.spans.district.as_ref().unwrap().chars,
+            0..3
+        );
+        assert_eq!(
+            candidates[1].address.spans.detail.as_ref().unwrap().bytes,
+            9..15
+        );
+        assert_eq!(
+            candidates[0].address.district_code,
+            Some("110105".to_string())
+        );
+        assert_eq!(
+            candidates[0].address.provenance.city,
+            Some(MatchSource::InferredFromChild)
+        );
+        assert_eq!(
+            candidates[0].reason,
+            CandidateReason::SharedName {
+                name: "朝阳区".to_string(),
+                count: 2
+            }
+        );
+        assert!((candidates.iter().map(|c| c.score).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // 简称同时对应城市和区县
+        let cities: Vec<_> = p
+            .parse_candidates("朝阳", 10)
+            .into_iter()
+            .map(|c| (c.address.city.unwrap(), c.address.district.unwrap()))
+            .collect();
+        assert_eq!(cities.len(), 3);
+        assert!(cities.contains(&("朝阳市".to_string(), "朝阳县".to_string())));
+
+        assert_eq!(p.parse_candidates("鼓楼区", 2).len(), 2);
+        assert!(p.parse_candidates("鼓楼区", 0).is_empty());
     }
 
     #[test]
-    fn test_parse_inner_mongolia_short() {
-        let p = parser();
-        let r = p.parse("内蒙古呼和浩特");
-
-        assert_eq!(r.province, Some("内蒙古自治区".to_string()));
-        assert_eq!(r.city, Some("呼和浩特市".to_string()));
+    fn test_parse_candidates_ranking() {
+        let p = town_parser();
+
+        // 乡镇街道位于其中一个候选
+        let candidates = p.parse_candidates("南山区粤海街道", 10);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].address.city, Some("深圳市".to_string()));
+        assert_eq!(
+            candidates[0].reason,
+            CandidateReason::TownMatched {
+                town: "粤海街道".to_string()
+            }
+        );
+        assert!(candidates[0].score > candidates[1].score);
+
+        // 唯一解读与 parse 一致
+        for address in ["吉林朝阳区", "北京市崇文区", "广东省", "科技园"] {
+            let candidates = p.parse_candidates(address, 10);
+            assert_eq!(candidates.len(), 1);
+            assert_eq!(candidates[0].address, p.parse(address));
+            assert_eq!(candidates[0].reason, CandidateReason::Unique);
+            assert_eq!(candidates[0].score, 1.0);
+        }
     }
 
-    // ==================== 不设区的市测试 ====================
+    // ==================== 港澳测试 ====================
 
     #[test]
-    fn test_parse_dongguan() {
-        // 东莞市没有区，直接是镇
+    fn test_parse_hong_kong() {
         let p = parser();
-        let r = p.parse("广东省东莞市长安镇");
+        let r = p.parse("香港九龙城区太子道西");
+        assert_eq!(r.province, Some("香港特别行政区".to_string()));
+        assert_eq!(r.city, Some("香港特别行政区".to_string()));
+        assert_eq!(r.district, Some("九龙城区".to_string()));
+        assert_eq!(r.detail, "太子道西");
+        assert_eq!(r.city_kind, Some(RegionKind::SpecialAdministrativeRegion));
+
+        // 片区名、繁体
+        let r = p.parse("香港新界沙田区大围");
+        assert_eq!(r.district, Some("沙田区".to_string()));
+        assert_eq!(r.detail, "大围");
+        let r = p.parse("香港特別行政區灣仔區告士打道");
+        assert_eq!(r.district, Some("湾仔区".to_string()));
+        assert_eq!(r.detail, "告士打道");
+
+        // 缺省特别行政区
+        let r = p.parse("深水埗区长沙湾道");
+        assert_eq!(r.province, Some("香港特别行政区".to_string()));
+        assert_eq!(r.district, Some("深水埗区".to_string()));
+
+        // 片区后没有区县时保留在详细地址中
+        let r = p.parse("香港九龍旺角");
+        assert_eq!(r.district, None);
+        assert_eq!(r.detail, "九龍旺角");
 
-        assert_eq!(r.province, Some("广东省".to_string()));
-        assert_eq!(r.city, Some("东莞市".to_string()));
-        // 长安镇可能在 district 或 detail 中
+        // 与内地同名简称的区县按所属地区区分
+        assert_eq!(p.parse("广东梅州大埔").district, Some("大埔县".to_string()));
+        assert_eq!(p.parse("香港大埔").district, Some("大埔区".to_string()));
     }
 
     #[test]
-    fn test_parse_zhongshan() {
+    fn test_parse_macau() {
         let p = parser();
-        let r = p.parse("广东省中山市小榄镇");
-
-        assert_eq!(r.province, Some("广东省".to_string()));
-        assert_eq!(r.city, Some("中山市".to_string()));
+        let r = p.parse("澳门氹仔");
+        assert_eq!(r.province, Some("澳门特别行政区".to_string()));
+        assert_eq!(r.city, Some("澳门特别行政区".to_string()));
+        assert_eq!(r.district, Some("嘉模堂区".to_string()));
+
+        let r = p.parse("澳門花地瑪堂區黑沙環");
+        assert_eq!(r.province, Some("澳门特别行政区".to_string()));
+        assert_eq!(r.district, Some("花地玛堂区".to_string()));
+        assert_eq!(r.detail, "黑沙環");
+
+        assert_eq!(
+            p.parse("澳门路环").district,
+            Some("圣方济各堂区".to_string())
+        );
     }
 
-    // ==================== 自治州测试 ====================
-
     #[test]
-    fn test_parse_autonomous_prefecture() {
+    fn test_parse_en_sar() {
         let p = parser();
-        let r = p.parse("云南省大理白族自治州大理市");
-
-        assert_eq!(r.province, Some("云南省".to_string()));
-        assert_eq!(r.city, Some("大理白族自治州".to_string()));
-        assert_eq!(r.district, Some("大理市".to_string()));
+        let r = p.parse_en("1 Sha Tin Road, Sha Tin, New Territories, Hong Kong");
+        assert_eq!(r.province, Some("香港特别行政区".to_string()));
+        assert_eq!(r.district, Some("沙田区".to_string()));
+        assert_eq!(r.detail, "1 Sha Tin Road");
+
+        let r = p.parse_en("Kowloon City, Kowloon, Hong Kong SAR");
+        assert_eq!(r.district, Some("九龙城区".to_string()));
+        let r = p.parse_en("Cathedral Parish, Macao");
+        assert_eq!(r.district, Some("大堂区".to_string()));
+        let r = p.parse_en("Taipa, Macau");
+        assert_eq!(r.district, Some("嘉模堂区".to_string()));
     }
 
     // ==================== 边界情况测试 ====================
@@ -690,7 +3167,231 @@ mod tests {
         let r = p.parse("  广东省  深圳市  南山区  ");
 
         assert_eq!(r.province, Some("广东省".to_string()));
-        // 注意：中间的空格会影响匹配，这是预期行为
+        // 地名之间的空格被跳过
+        assert_eq!(r.city, Some("深圳市".to_string()));
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.detail, "");
+    }
+
+    #[test]
+    fn test_parse_preprocess() {
+        let p = town_parser();
+        let input = "\u{feff}广东省，深圳市、南山区\u{200b} 粤海街道／科技园１２号";
+        let r = p.parse(input);
+        assert_eq!(r.city, Some("深圳市".to_string()));
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.town, Some("粤海街道".to_string()));
+        assert_eq!(r.detail, "科技园12号");
+        // 位置对应原始输入
+        assert_eq!(&input[r.spans.city.clone().unwrap().bytes], "深圳市");
+        assert_eq!(r.spans.province.clone().unwrap().chars, 1..4);
+        let detail = r.spans.detail.unwrap();
+        assert_eq!(&input[detail.bytes], "科技园１２号");
+        assert_eq!(detail.chars, 19..25);
+
+        let r = p.parse_en("Nanshan District，Shenzhen，Guangdong\u{200b}");
+        assert_eq!(r.province, Some("广东省".to_string()));
+
+        // 关闭预处理
+        let raw = AddressParser::with_preprocess(PreprocessOptions::none());
+        let r = raw.parse("广东省 深圳市 科技园１２号");
+        assert_eq!(r.city, None);
+        assert_eq!(r.detail, "深圳市 科技园１２号");
+        let r = AddressParser::with_preprocess(PreprocessOptions {
+            separators: false,
+            ..Default::default()
+        })
+        .parse("广东省深圳市科技园１２号");
+        assert_eq!(r.detail, "科技园12号");
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let p = parser();
+        assert!(p.try_parse("深圳南山").is_ok());
+        assert!(p.try_parse("朝阳区望京").is_ok());
+        assert_eq!(
+            p.try_parse("  "),
+            Err(ParseError::NotFound("  ".to_string()))
+        );
+        assert_eq!(
+            p.try_parse("浙江省深圳市南山区"),
+            Err(ParseError::CityNotInProvince {
+                province: "浙江省".to_string(),
+                city: "深圳市".to_string(),
+            })
+        );
+        assert_eq!(
+            p.try_parse("深圳市西湖区文三路"),
+            Err(ParseError::DistrictNotInCity {
+                city: "深圳市".to_string(),
+                district: "西湖区".to_string(),
+            })
+        );
+        assert_eq!(
+            p.try_parse("广东省西湖区"),
+            Err(ParseError::DistrictNotInProvince {
+                province: "广东省".to_string(),
+                district: "西湖区".to_string(),
+            })
+        );
+        // 唯一的区县不能改写写明的省份
+        assert_eq!(
+            p.try_parse("广东省海淀区"),
+            Err(ParseError::DistrictNotInProvince {
+                province: "广东省".to_string(),
+                district: "海淀区".to_string(),
+            })
+        );
+        assert_eq!(
+            p.try_parse("浙江省浦东新区"),
+            Err(ParseError::DistrictNotInProvince {
+                province: "浙江省".to_string(),
+                district: "浦东新区".to_string(),
+            })
+        );
+        let r = p.parse("浙江省浦东新区");
+        assert_eq!(r.province, Some("浙江省".to_string()));
+        assert_eq!(r.district, None);
+        // 省内的乡镇街道仍可确定城市
+        let r = p.try_parse("广东省东城街道").unwrap();
+        assert_eq!(r.city, Some("东莞市".to_string()));
+        assert_eq!(r.town, Some("东城街道".to_string()));
+        // 详细地址中的简称不算矛盾
+        assert!(p.try_parse("广东省深圳市南山区杭州路").is_ok());
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let p = parser();
+        assert!(p.parse_strict("北京市朝阳区").is_ok());
+        assert!(p.parse_strict("东莞市长安镇").is_ok());
+        match p.parse_strict("朝阳区望京") {
+            Err(ParseError::AmbiguousDistrict {
+                district,
+                candidates,
+            }) => {
+                assert_eq!(district, "朝阳区");
+                let cities: Vec<_> = candidates.iter().map(|r| r.city.as_str()).collect();
+                assert_eq!(cities, ["北京市", "长春市"]);
+                assert_eq!(candidates[0].district_code.as_deref(), Some("110105"));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(
+            p.parse_strict("广东省深圳市"),
+            Err(ParseError::Incomplete {
+                address: "广东省深圳市".to_string(),
+                missing: RegionLevel::District,
+            })
+        );
+        assert!(matches!(
+            p.parse_strict("广东省"),
+            Err(ParseError::Incomplete { .. })
+        ));
+        assert!(matches!(
+            p.parse_strict("广东省杭州市"),
+            Err(ParseError::CityNotInProvince { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_recipient() {
+        let p = parser();
+        let r = p.parse_recipient("广东省深圳市南山区科技园路1号 李四 0755-86013388 518000");
+        assert_eq!(r.name, Some("李四".to_string()));
+        assert_eq!(r.phones.len(), 1);
+        assert_eq!(r.phones[0].number, "0755-86013388");
+        assert_eq!(r.phones[0].kind, crate::PhoneKind::Landline);
+        assert_eq!(r.postal_code, Some("518000".to_string()));
+        assert_eq!(r.address.city, Some("深圳市".to_string()));
+        assert_eq!(r.address.detail, "科技园路1号");
+
+        // 带标签、全角字符，位置对应原始输入
+        let text = "收件人：王五\n手机号码：１３９ １２３４ ５６７８\n所在地区：浙江省 杭州市 西湖区\n详细地址：文三路100号";
+        let r = p.parse_recipient(text);
+        assert_eq!(r.name, Some("王五".to_string()));
+        assert_eq!(r.phones[0].number, "13912345678");
+        assert_eq!(r.address.district, Some("西湖区".to_string()));
+        assert_eq!(r.address.detail, "文三路100号");
+        assert_eq!(&text[r.address.spans.province.unwrap().bytes], "浙江省");
+
+        // 姓名在后、紧贴地址或含间隔号
+        let r = p.parse_recipient("18612345678 北京市朝阳区望京SOHO T1 欧阳娜娜");
+        assert_eq!(r.name, Some("欧阳娜娜".to_string()));
+        assert_eq!(r.address.detail, "望京SOHO T1");
+        let r = p.parse_recipient("赵六广东省深圳市南山区科技园");
+        assert_eq!(r.name, Some("赵六".to_string()));
+        assert_eq!(r.address.district, Some("南山区".to_string()));
+        let r = p.parse_recipient("买买提·艾力,新疆乌鲁木齐市天山区人民路1号,+86 139-9999-9999");
+        assert_eq!(r.name, Some("买买提·艾力".to_string()));
+        assert_eq!(r.phones[0].number, "13999999999");
+        assert_eq!(r.address.detail, "人民路1号");
+
+        let r = p.parse_recipient("13800138000");
+        assert_eq!(r.name, None);
+        assert_eq!(r.address.province, None);
+    }
+
+    #[test]
+    fn test_extract() {
+        let p = town_parser();
+        let text = "收件人张三 13800138000 广东省深圳市南山区科技园";
+        let found = p.extract(text);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            &text[found[0].span.bytes.clone()],
+            "广东省深圳市南山区科技园"
+        );
+        assert_eq!(found[0].span.chars, 18..30);
+        let district = found[0].address.spans.district.clone().unwrap();
+        assert_eq!(&text[district.bytes], "南山区");
+
+        // 多个地址，详细地址止于标点、电话号码或下一个地址
+        let text =
+            "我在广东省深圳市南山区科技园，明天见。\n寄到杭州市西湖区文三路100号 13912345678\n\
+                    上海市浦东新区和北京市朝阳区";
+        let found = p.extract(text);
+        let addresses: Vec<_> = found.iter().map(|e| &text[e.span.bytes.clone()]).collect();
+        assert_eq!(
+            addresses,
+            [
+                "广东省深圳市南山区科技园",
+                "杭州市西湖区文三路100号",
+                "上海市浦东新区",
+                "北京市朝阳区",
+            ]
+        );
+        assert_eq!(found[1].address.province, Some("浙江省".to_string()));
+        assert_eq!(found[3].address.detail, "");
+
+        let found = p.extract("我住在上海市浦东新区和北京市朝阳区");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].address.detail, "");
+
+        // 简称写出的两级地名同样是下一个地址的开始
+        let found = p.extract("深圳南山和杭州西湖都不错");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].address.district, Some("南山区".to_string()));
+        assert_eq!(found[0].address.detail, "");
+        assert_eq!(found[1].address.district, Some("西湖区".to_string()));
+
+        // 不在两个地址之间的"和"保留在详细地址中
+        let found = p.extract("广东省深圳市南山区和平路1号");
+        assert_eq!(found[0].address.detail, "和平路1号");
+
+        // 只有一级地名的不算地址
+        assert!(p.extract("明天去中山玩，路过中山路").is_empty());
+        assert!(p.extract("").is_empty());
+
+        // 位置对应预处理前的原文
+        let text = "地址：廣東省深圳市南山區粵海街道科技園１２號";
+        let found = p.extract(text);
+        assert_eq!(found[0].address.town, Some("粤海街道".to_string()));
+        assert_eq!(
+            &text[found[0].span.bytes.clone()],
+            "廣東省深圳市南山區粵海街道科技園１２號"
+        );
     }
 
     // ==================== 标准化测试 ====================
@@ -797,6 +3498,151 @@ mod tests {
         assert_eq!(r2.district, Some("朝阳区".to_string()));
     }
 
+    // ==================== 行政区划代码测试 ====================
+
+    #[test]
+    fn test_parse_adcodes() {
+        let p = parser();
+
+        let r = p.parse("广东省深圳市南山区科技园");
+        assert_eq!(r.province_code, Some("440000".to_string()));
+        assert_eq!(r.city_code, Some("440300".to_string()));
+        assert_eq!(r.district_code, Some("440305".to_string()));
+
+        // 同名区县由代码区分
+        let r1 = p.parse("北京市朝阳区");
+        let r2 = p.parse("吉林省长春市朝阳区");
+        assert_eq!(r1.district_code, Some("110105".to_string()));
+        assert_eq!(r2.district_code, Some("220104".to_string()));
+        assert_eq!(
+            p.parse("杭州市西湖区").district_code,
+            Some("330106".to_string())
+        );
+
+        // 未解析出的层级没有代码
+        let r = p.parse("某某路123号");
+        assert_eq!(r.province_code, None);
+        assert_eq!(r.city_code, None);
+    }
+
+    #[test]
+    fn test_region_by_code() {
+        let p = parser();
+
+        let region = p.region_by_code("440300").unwrap();
+        assert_eq!(region.city, "深圳市");
+        assert_eq!(region.district, None);
+
+        let region = p.region_by_code("220104").unwrap();
+        assert_eq!(region.city, "长春市");
+        assert_eq!(region.district, Some("朝阳区".to_string()));
+
+        assert!(p.region_by_code("440000").is_none());
+        assert!(p.region_by_code("999999").is_none());
+    }
+
+    // ==================== 中心点坐标测试 ====================
+
+    #[test]
+    fn test_region_centroid() {
+        let p = parser();
+
+        let district = p
+            .region_centroid("广东省", "深圳市", Some("南山区"))
+            .unwrap();
+        let city = p.region_centroid("广东省", "深圳市", None).unwrap();
+        assert_ne!(district, city);
+
+        let province = p.region_centroid("广东省", "", None).unwrap();
+        assert_ne!(province, city);
+
+        // 地级市、自治州覆盖大部分，区县只覆盖一部分
+        let huzhou = p.region_centroid("浙江省", "湖州市", None).unwrap();
+        assert!((119.5..120.5).contains(&huzhou.lng));
+        assert!(p
+            .region_centroid("四川省", "凉山彝族自治州", None)
+            .is_some());
+
+        // 没有坐标时不退回上级
+        assert_eq!(p.region_centroid("广东省", "深圳市", Some("坪山区")), None);
+        assert_eq!(p.region_centroid("台湾省", "台北市", None), None);
+        assert_eq!(p.region_centroid("火星", "", None), None);
+    }
+
+    #[test]
+    fn test_parsed_address_centroid() {
+        let p = parser();
+        let (level, c) = p.parse("北京市朝阳区望京").centroid(&p).unwrap();
+        assert_eq!(level, RegionLevel::District);
+        assert!((116.0..117.0).contains(&c.lng));
+        assert!((39.0..41.0).contains(&c.lat));
+
+        // 区县没有坐标时返回城市坐标，并标明层级
+        let (level, c) = p.parse("深圳市坪山区").centroid(&p).unwrap();
+        assert_eq!(level, RegionLevel::City);
+        assert_eq!(Some(c), p.region_centroid("广东省", "深圳市", None));
+
+        assert_eq!(p.parse("某某路123号").centroid(&p), None);
+    }
+
+    // ==================== 邮政编码、电话区号测试 ====================
+
+    #[test]
+    fn test_postal_and_area_code() {
+        let p = parser();
+
+        assert_eq!(
+            p.postal_code("北京市", "北京市", Some("朝阳区")),
+            Some("100020")
+        );
+        // 区县无数据时退回到城市
+        assert_eq!(
+            p.postal_code("北京市", "北京市", Some("东城区")),
+            Some("100000")
+        );
+        assert_eq!(p.area_code("广东省", "深圳市", None), Some("0755"));
+        assert_eq!(p.area_code("广东省", "韶关市", None), Some("0751"));
+        assert_eq!(p.area_code("辽宁省", "朝阳市", None), Some("0421"));
+        assert_eq!(p.area_code("海南省", "三亚市", None), Some("0898"));
+        // 省份不符时不返回
+        assert_eq!(p.area_code("北京市", "朝阳市", None), None);
+        assert_eq!(p.postal_code("广东省", "韶关市", None), None);
+
+        let r = p.parse("广东省深圳市南山区科技园");
+        assert_eq!(r.postal_code(&p), Some("518000"));
+        assert_eq!(r.area_code(&p), Some("0755"));
+        assert_eq!(p.parse("辽宁省朝阳市双塔区").area_code(&p), Some("0421"));
+    }
+
+    #[test]
+    fn test_regions_by_postal_and_area_code() {
+        let p = parser();
+
+        // 北京市及其全部区
+        let regions = p.regions_by_area_code("010");
+        assert_eq!(regions.len(), 17);
+        assert!(regions.iter().all(|r| r.city == "北京市"));
+
+        // 共用区号的多个城市
+        let regions = p.regions_by_area_code("024");
+        let mut cities: Vec<&str> = regions.iter().map(|r| r.city.as_str()).collect();
+        cities.dedup();
+        assert_eq!(cities, ["抚顺市", "沈阳市", "铁岭市"]);
+
+        let regions = p.regions_by_postal_code("518000");
+        assert!(regions.iter().all(|r| r.city == "深圳市"));
+        assert!(regions.iter().any(|r| r.district.is_none()));
+        assert!(regions
+            .iter()
+            .any(|r| r.district.as_deref() == Some("南山区")));
+        assert!(!p
+            .regions_by_postal_code("100000")
+            .iter()
+            .any(|r| r.district.as_deref() == Some("朝阳区")));
+
+        assert!(p.regions_by_area_code("9999").is_empty());
+    }
+
     // ==================== 全局解析器测试 ====================
 
     #[test]
@@ -949,4 +3795,331 @@ mod tests {
         let r = p.parse("宝安区");
         assert_eq!(r.district, Some("宝安区".to_string()));
     }
+
+    #[test]
+    fn test_parse_pinyin() {
+        let p = AddressParser::with_pinyin();
+
+        let r = p.parse("guangdong shenzhen nanshan");
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.city, Some("深圳市".to_string()));
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.district_code, Some("440305".to_string()));
+
+        // 大小写、分隔符不敏感，剩余部分作为详细地址
+        let r = p.parse("ShenZhen-NanShan Keji Road 1");
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.detail, "Keji Road 1");
+
+        // 直辖市
+        let r = p.parse("beijing chaoyang");
+        assert_eq!(r.city, Some("北京市".to_string()));
+        assert_eq!(r.district, Some("朝阳区".to_string()));
+
+        // 汉字地址不受影响
+        let r = p.parse("深圳市南山区");
+        assert_eq!(r.district, Some("南山区".to_string()));
+    }
+
+    #[test]
+    fn test_parse_pinyin_initials() {
+        let p = AddressParser::with_pinyin();
+
+        for input in ["gd sz", "gdsz", "GD SZ"] {
+            let r = p.parse(input);
+            assert_eq!(r.province, Some("广东省".to_string()), "{}", input);
+            assert_eq!(r.city, Some("深圳市".to_string()), "{}", input);
+        }
+
+        // 首字母 sz 对应多个城市，由区县确定
+        let r = p.parse("sz nanshan");
+        assert_eq!(r.city, Some("深圳市".to_string()));
+        assert_eq!(r.district, Some("南山区".to_string()));
+    }
+
+    #[test]
+    fn test_parse_pinyin_ambiguous() {
+        let p = AddressParser::with_pinyin();
+
+        // 山西、陕西同音，由城市区分
+        let r = p.parse("shanxi taiyuan");
+        assert_eq!(r.province, Some("山西省".to_string()));
+        let r = p.parse("shanxi xian");
+        assert_eq!(r.province, Some("陕西省".to_string()));
+        assert_eq!(r.city, Some("西安市".to_string()));
+        let r = p.parse("shaanxi");
+        assert_eq!(r.province, Some("陕西省".to_string()));
+
+        // 无法区分时不做假设
+        let r = p.parse("shanxi");
+        assert_eq!(r.province, None);
+
+        // 不能止于音节中间（"sh" 不应匹配为上海）
+        let r = p.parse("shenzhen");
+        assert_eq!(r.city, Some("深圳市".to_string()));
+    }
+
+    #[test]
+    fn test_pinyin_disabled_by_default() {
+        let r = parser().parse("guangdong shenzhen");
+        assert_eq!(r.province, None);
+        assert_eq!(r.detail, "guangdong shenzhen");
+    }
+
+    #[test]
+    fn test_parse_en() {
+        let p = parser();
+
+        let r = p.parse_en("Room 101, 1 Keji Rd, Nanshan District, Shenzhen, Guangdong, China");
+        assert_eq!(r.province, Some("广东省".to_string()));
+        assert_eq!(r.city, Some("深圳市".to_string()));
+        assert_eq!(r.district, Some("南山区".to_string()));
+        assert_eq!(r.district_code, Some("440305".to_string()));
+        assert_eq!(r.detail, "Room 101, 1 Keji Rd");
+
+        // 直辖市、邮编、通名
+        let r = p.parse_en("88 Century Ave, Pudong New Area, Shanghai 200120, PRC");
+        assert_eq!(r.city, Some("上海市".to_string()));
+        assert_eq!(r.district, Some("浦东新区".to_string()));
+        assert_eq!(r.detail, "88 Century Ave");
+
+        let r = p.parse_en("Xihu District, Hangzhou City, Zhejiang Province, P.R. China 310000");
+        assert_eq!(r.province, Some("浙江省".to_string()));
+        assert_eq!(r.district, Some("西湖区".to_string()));
+        assert_eq!(r.detail, "");
+    }
+
+    #[test]
+    fn test_parse_en_official_names() {
+        let p = parser();
+
+        let r = p.parse_en("Hohhot, Inner Mongolia");
+        assert_eq!(r.province, Some("内蒙古自治区".to_string()));
+        assert_eq!(r.city, Some("呼和浩特市".to_string()));
+
+        let r = p.parse_en("Dali Bai Autonomous Prefecture, Yunnan");
+        assert_eq!(r.city, Some("大理白族自治州".to_string()));
+
+        // 陕西省的官方译名为 Shaanxi，Shanxi 只指山西省
+        assert_eq!(p.parse_en("Shanxi").province, Some("山西省".to_string()));
+        let r = p.parse_en("Shanxi Province");
+        assert_eq!(r.province, Some("山西省".to_string()));
+        assert_eq!(r.city, None);
+        let r = p.parse_en("Taiyuan, Shanxi Province");
+        assert_eq!(r.province, Some("山西省".to_string()));
+        assert_eq!(r.city, Some("太原市".to_string()));
+        let r = p.parse_en("Xi'an, Shaanxi Province");
+        assert_eq!(r.province, Some("陕西省".to_string()));
+        assert_eq!(r.city, Some("西安市".to_string()));
+
+        // 县级市
+        let r = p.parse_en("Kunshan City, Suzhou, Jiangsu");
+        assert_eq!(r.city, Some("苏州市".to_string()));
+        assert_eq!(r.district, Some("昆山市".to_string()));
+
+        // 通名不符时不匹配
+        let r = p.parse_en("Nanshan County, Shenzhen");
+        assert_eq!(r.city, Some("深圳市".to_string()));
+        assert_eq!(r.district, None);
+        assert_eq!(r.detail, "Nanshan County");
+
+        let r = p.parse_en("1 Main St, Springfield");
+        assert_eq!(r.province, None);
+        assert_eq!(r.detail, "1 Main St, Springfield");
+    }
+
+    #[test]
+    fn test_parse_en_historical() {
+        let p = parser();
+
+        let r = p.parse_en("Fancheng District, Xiangfan, Hubei");
+        assert_eq!(r.city, Some("襄阳市".to_string()));
+        assert_eq!(r.district, Some("樊城区".to_string()));
+        assert_eq!(r.provenance.city, Some(MatchSource::Historical));
+        assert_eq!(r.historical[0].old_name, "襄樊市樊城区");
+        assert_eq!(r.historical[0].new_name, "襄阳市樊城区");
+
+        let r = p.parse_en("Chongwen District, Beijing");
+        assert_eq!(r.district, Some("东城区".to_string()));
+        assert_eq!(r.provenance.district, Some(MatchSource::Historical));
+
+        let r = p.parse_en("Laicheng District, Laiwu, Shandong");
+        assert_eq!(r.city, Some("济南市".to_string()));
+        assert_eq!(r.district, Some("莱芜区".to_string()));
+        assert_eq!(r.historical.len(), 1);
+
+        let r = p.parse_en("Hanshan County, Chaohu, Anhui");
+        assert_eq!(r.city, Some("马鞍山市".to_string()));
+        assert_eq!(r.historical[0].old_name, "巢湖市含山县");
+
+        // 现行名称优先
+        let r = p.parse_en("Wuwei, Wuhu, Anhui");
+        assert_eq!(r.district, Some("无为市".to_string()));
+        assert!(!r.is_historical());
+        let r = p.parse_en("Wuwei County, Wuhu, Anhui");
+        assert_eq!(r.district, Some("无为市".to_string()));
+        assert_eq!(r.historical[0].old_name, "芜湖市无为县");
+    }
+
+    #[test]
+    fn test_parse_pinyin_historical() {
+        let p = AddressParser::with_pinyin();
+
+        let r = p.parse("hubei xiangfan fancheng");
+        assert_eq!(r.city, Some("襄阳市".to_string()));
+        assert_eq!(r.district, Some("樊城区".to_string()));
+        assert_eq!(r.historical[0].old_name, "襄樊市樊城区");
+
+        let r = p.parse("xiangfan");
+        assert_eq!(r.city, Some("襄阳市".to_string()));
+        assert_eq!(r.provenance.city, Some(MatchSource::Historical));
+
+        let r = p.parse("beijing chongwen");
+        assert_eq!(r.district, Some("东城区".to_string()));
+        assert!(r.is_historical());
+
+        let r = p.parse("shandong laiwu gangcheng");
+        assert_eq!(r.city, Some("济南市".to_string()));
+        assert_eq!(r.district, Some("钢城区".to_string()));
+        assert_eq!(r.historical[0].old_name, "莱芜市钢城区");
+    }
+
+    #[test]
+    fn test_parse_historical_district() {
+        let p = parser();
+
+        let r = p.parse("北京市崇文区天坛路");
+        assert_eq!(r.city, Some("北京市".to_string()));
+        assert_eq!(r.district, Some("东城区".to_string()));
+        assert_eq!(r.district_code, Some("110101".to_string()));
+        assert_eq!(r.detail, "天坛路");
+        assert!(r.is_historical());
+        assert_eq!(r.historical[0].old_name, "北京市崇文区");
+        assert_eq!(r.historical[0].new_name, "北京市东城区");
+        assert_eq!(r.historical[0].effective, "2010-07-01");
+
+        let r = p.parse("上海闸北");
+        assert_eq!(r.district, Some("静安区".to_string()));
+
+        // 现行名称不受影响
+        let r = p.parse("北京市东城区");
+        assert!(!r.is_historical());
+    }
+
+    #[test]
+    fn test_parse_historical_city() {
+        let p = parser();
+
+        // 更名
+        let r = p.parse("湖北省襄樊市樊城区");
+        assert_eq!(r.city, Some("襄阳市".to_string()));
+        assert_eq!(r.district, Some("樊城区".to_string()));
+        assert_eq!(r.historical[0].old_name, "襄樊市樊城区");
+
+        let r = p.parse("湖北省襄樊市襄阳区");
+        assert_eq!(r.district, Some("襄州区".to_string()));
+        assert_eq!(r.historical.len(), 1);
+
+        // 撤销的地级市，下属区县分别划入其他城市
+        let r = p.parse("安徽省巢湖市和县");
+        assert_eq!(r.city, Some("马鞍山市".to_string()));
+        assert_eq!(r.district, Some("和县".to_string()));
+        assert_eq!(r.historical.len(), 1);
+        assert_eq!(r.historical[0].old_name, "巢湖市和县");
+
+        let r = p.parse("巢湖市庐江县");
+        assert_eq!(r.city, Some("合肥市".to_string()));
+        assert_eq!(r.district, Some("庐江县".to_string()));
+
+        // 与现行县级市同名时按原地级市换算，承继地区即该县级市
+        let r = p.parse("安徽省巢湖市");
+        assert_eq!(r.city, Some("合肥市".to_string()));
+        assert_eq!(r.district, Some("巢湖市".to_string()));
+        assert_eq!(r.historical[0].old_name, "巢湖市");
+        assert_eq!(r.historical[0].new_name, "合肥市巢湖市");
+        assert_eq!(r.provenance.city, Some(MatchSource::Historical));
+
+        // 县级市的现行名称不受影响
+        let r = p.parse("安徽省合肥市巢湖市");
+        assert!(!r.is_historical());
+
+        // 并入其他城市
+        let r = p.parse("山东省莱芜市钢城区");
+        assert_eq!(r.city, Some("济南市".to_string()));
+        assert_eq!(r.district, Some("钢城区".to_string()));
+        assert_eq!(r.historical[0].old_name, "莱芜市钢城区");
+        assert_eq!(r.historical[0].effective, "2019-01-09");
+
+        let r = p.parse("莱芜市莱城区");
+        assert_eq!(r.district, Some("莱芜区".to_string()));
+        assert_eq!(r.historical.len(), 1);
+
+        // 地区改市，下属县级单位同时更名
+        let r = p.parse("甘肃省定西地区漳县");
+        assert_eq!(r.city, Some("定西市".to_string()));
+        assert_eq!(r.district, Some("漳县".to_string()));
+        let r = p.parse("内蒙古乌兰察布盟集宁市");
+        assert_eq!(r.district, Some("集宁区".to_string()));
+        assert_eq!(r.detail, "");
+    }
+
+    #[test]
+    fn test_parse_historical_chain() {
+        let p = parser();
+
+        // 先改隶芜湖市，后撤县设市：按所写城市取对应的调整
+        let r = p.parse("安徽省芜湖市无为县");
+        assert_eq!(r.district, Some("无为市".to_string()));
+        assert_eq!(r.detail, "");
+        assert_eq!(r.historical[0].old_name, "芜湖市无为县");
+
+        let r = p.parse("安徽省巢湖市无为县");
+        assert_eq!(r.city, Some("芜湖市".to_string()));
+        assert_eq!(r.district, Some("无为市".to_string()));
+        assert_eq!(r.historical[0].old_name, "巢湖市无为县");
+
+        // 城市未知时取最近一次调整
+        let r = p.parse("无为县");
+        assert_eq!(r.historical[0].old_name, "芜湖市无为县");
+
+        // 撤县设区
+        let r = p.parse("浙江省杭州市江干区");
+        assert_eq!(r.district, Some("上城区".to_string()));
+        assert!(r.is_historical());
+    }
+
+    #[cfg(feature = "vintages")]
+    #[test]
+    fn test_with_vintage() {
+        let p = AddressParser::with_vintage(2009).unwrap();
+
+        let r = p.parse("湖北省襄樊市樊城区");
+        assert_eq!(r.city, Some("襄樊市".to_string()));
+        assert_eq!(r.district, Some("樊城区".to_string()));
+        assert!(!r.is_historical());
+
+        // 2009 年尚无襄阳市，"襄阳"指襄樊市襄阳区
+        let r = p.parse("湖北省襄阳市");
+        assert_eq!(r.city, Some("襄樊市".to_string()));
+        assert_eq!(r.district, Some("襄阳区".to_string()));
+
+        // 2009 年已生效的调整仍会换算
+        let r = p.parse("上海市南汇区");
+        assert_eq!(r.district, Some("浦东新区".to_string()));
+        assert!(r.is_historical());
+
+        // 2016 年的代码表：尚未撤县设区
+        let p = AddressParser::with_vintage(2016).unwrap();
+        let r = p.parse("四川省成都市郫县");
+        assert_eq!(r.district, Some("郫县".to_string()));
+        assert_eq!(r.district_code, Some("510124".to_string()));
+        assert!(!r.is_historical());
+
+        for year in [2019, 2030] {
+            assert_eq!(
+                AddressParser::with_vintage(year).err(),
+                Some(ParseError::UnsupportedVintage(year))
+            );
+        }
+    }
 }
@@ -0,0 +1,307 @@
+//! 地名拼音转换与拼音输入匹配
+
+use crate::data::{load_pinyin, Redirect, RegionIndex};
+use crate::trie::Trie;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 全局拼音表
+static PINYIN_TABLE: Lazy<PinyinTable> = Lazy::new(PinyinTable::load);
+
+/// 地名的拼音（不带声调，全小写）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pinyin {
+    /// 全拼，如 "guangdongsheng"
+    pub full: String,
+    /// 首字母，如 "gds"
+    pub initials: String,
+}
+
+impl Pinyin {
+    /// 将地名转换为拼音
+    ///
+    /// 拼音表只收录内置行政区划用到的汉字，表外汉字会被忽略，ASCII 字母和数字原样保留。
+    ///
+    /// ```rust
+    /// use cpca::Pinyin;
+    ///
+    /// let pinyin = Pinyin::from_hanzi("六安市");
+    /// assert_eq!(pinyin.full, "luanshi");
+    /// assert_eq!(pinyin.initials, "las");
+    /// ```
+    pub fn from_hanzi(text: &str) -> Self {
+        let syllables = PINYIN_TABLE.syllables(text);
+        Self {
+            full: syllables.concat(),
+            initials: syllables.iter().filter_map(|s| s.chars().next()).collect(),
+        }
+    }
+}
+
+/// 拼音表：单字读音，以及读音特殊的地名词
+struct PinyinTable {
+    chars: HashMap<char, &'static str>,
+    words: HashMap<&'static str, Vec<&'static str>>,
+    /// 地名词的最大字数
+    max_word_len: usize,
+}
+
+impl PinyinTable {
+    fn load() -> Self {
+        let mut chars = HashMap::new();
+        let mut words = HashMap::new();
+        for (hanzi, pinyin) in load_pinyin() {
+            let mut it = hanzi.chars();
+            match (it.next(), it.next()) {
+                (Some(ch), None) => {
+                    chars.insert(ch, pinyin);
+                }
+                _ => {
+                    words.insert(hanzi, pinyin.split_whitespace().collect());
+                }
+            }
+        }
+        let max_word_len = words.keys().map(|w| w.chars().count()).max().unwrap_or(0);
+        Self {
+            chars,
+            words,
+            max_word_len,
+        }
+    }
+
+    /// 逐音节转换，优先匹配最长的地名词
+    fn syllables(&self, text: &str) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut syllables = Vec::new();
+        let mut i = 0;
+
+        'outer: while i < chars.len() {
+            for len in (2..=self.max_word_len.min(chars.len() - i)).rev() {
+                let word: String = chars[i..i + len].iter().collect();
+                if let Some(pinyin) = self.words.get(word.as_str()) {
+                    syllables.extend(pinyin.iter().map(|s| s.to_string()));
+                    i += len;
+                    continue 'outer;
+                }
+            }
+            let ch = chars[i];
+            if let Some(pinyin) = self.chars.get(&ch) {
+                syllables.push(pinyin.to_string());
+            } else if ch.is_ascii_alphanumeric() {
+                syllables.push(ch.to_ascii_lowercase().to_string());
+            }
+            i += 1;
+        }
+
+        syllables
+    }
+}
+
+/// 两个字是否同音（不计声调），只能判断拼音表中收录的字
+pub(crate) fn is_homophone(a: char, b: char) -> bool {
+    match (PINYIN_TABLE.chars.get(&a), PINYIN_TABLE.chars.get(&b)) {
+        (Some(x), Some(y)) => x == y,
+        _ => false,
+    }
+}
+
+/// 拼音输入中可忽略的分隔符
+fn is_separator(ch: char) -> bool {
+    ch.is_whitespace() || matches!(ch, '-' | '\'' | ',' | '，' | '·' | '/')
+}
+
+/// 拼音前缀树（全拼与首字母），同一拼音可能对应多个地名
+pub(crate) struct PinyinTries {
+    pub province: Trie<Vec<String>>,
+    pub city: Trie<Vec<String>>,
+    pub district: Trie<Vec<String>>,
+}
+
+impl PinyinTries {
+    /// 按与汉字前缀树相同的简称规则构建，已撤销或更名的城市、区县一并收录
+    pub fn build(
+        index: &RegionIndex,
+        aliases: &HashMap<&'static str, &'static str>,
+        history: &[Redirect],
+    ) -> Self {
+        let mut provinces = HashMap::new();
+        for province in &index.provinces {
+            add_keys(&mut provinces, province, province);
+            for (short, full) in aliases {
+                if *full == province && short.chars().count() >= 2 {
+                    add_keys(&mut provinces, short, province);
+                }
+            }
+        }
+        // 陕西的官方拼写，用于区分山西
+        provinces
+            .entry("shaanxi".to_string())
+            .or_default()
+            .push("陕西省".to_string());
+
+        let old_cities = history
+            .iter()
+            .map(|r| &r.city)
+            .filter(|c| !index.cities.contains(*c));
+        let mut cities = HashMap::new();
+        for city in index.cities.iter().chain(old_cities) {
+            add_keys(&mut cities, city, city);
+            if let Some(short) = city.strip_suffix('市') {
+                add_keys(&mut cities, short, city);
+            }
+        }
+
+        let old_districts = history.iter().filter_map(|r| r.district.as_ref());
+        let mut districts = HashMap::new();
+        for district in index.districts.iter().chain(old_districts) {
+            add_keys(&mut districts, district, district);
+            for suffix in ['区', '县', '市', '旗'] {
+                if let Some(short) = district.strip_suffix(suffix) {
+                    if short.chars().count() >= 2 {
+                        add_keys(&mut districts, short, district);
+                    }
+                }
+            }
+        }
+
+        Self {
+            province: into_trie(provinces),
+            city: into_trie(cities),
+            district: into_trie(districts),
+        }
+    }
+}
+
+/// 登记地名的全拼与首字母（单个字母的首字母过于宽泛，不收录）
+fn add_keys(keys: &mut HashMap<String, Vec<String>>, name: &str, normalized: &str) {
+    let pinyin = Pinyin::from_hanzi(name);
+    let mut add = |key: String| {
+        let names = keys.entry(key).or_default();
+        if !names.iter().any(|n| n == normalized) {
+            names.push(normalized.to_string());
+        }
+    };
+    if pinyin.initials.len() >= 2 {
+        add(pinyin.initials);
+    }
+    add(pinyin.full);
+}
+
+fn into_trie(keys: HashMap<String, Vec<String>>) -> Trie<Vec<String>> {
+    let mut trie = Trie::new();
+    for (key, mut names) in keys {
+        names.sort();
+        trie.insert(&key, names);
+    }
+    trie
+}
+
+/// 拼音输入：去掉分隔符、转为小写后的紧凑形式
+pub(crate) struct PinyinInput {
+    /// 紧凑拼音串
+    pub compact: String,
+    /// 紧凑串第 i 个字母之后在原文中的字节位置
+    ends: Vec<usize>,
+    /// 紧凑串第 i 个字母之后是否为词边界（分隔符、非字母或结尾）
+    boundaries: Vec<bool>,
+}
+
+impl PinyinInput {
+    /// 从原文开头提取连续的拼音部分
+    pub fn new(text: &str) -> Self {
+        let mut compact = String::new();
+        let mut ends = Vec::new();
+        let mut boundaries = Vec::new();
+
+        for (pos, ch) in text.char_indices() {
+            if ch.is_ascii_alphabetic() {
+                compact.push(ch.to_ascii_lowercase());
+                ends.push(pos + 1);
+                boundaries.push(false);
+            } else if is_separator(ch) {
+                if let Some(last) = boundaries.last_mut() {
+                    *last = true;
+                }
+            } else {
+                break;
+            }
+        }
+        if let Some(last) = boundaries.last_mut() {
+            *last = true;
+        }
+
+        Self {
+            compact,
+            ends,
+            boundaries,
+        }
+    }
+
+    /// 紧凑串前 `len` 个字母在原文中的结束位置
+    pub fn original_end(&self, len: usize) -> usize {
+        if len == 0 {
+            0
+        } else {
+            self.ends[len - 1]
+        }
+    }
+
+    /// 紧凑串第 `start` 至 `end` 个字母在原文中的字节范围
+    pub fn original_span(&self, start: usize, end: usize) -> Range<usize> {
+        self.ends[start] - 1..self.ends[end - 1]
+    }
+
+    /// 紧凑串前 `len` 个字母之后是否为词边界
+    pub fn is_boundary(&self, len: usize) -> bool {
+        len == 0 || self.boundaries[len - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::load_regions;
+
+    #[test]
+    fn test_from_hanzi() {
+        assert_eq!(Pinyin::from_hanzi("广东省").full, "guangdongsheng");
+        assert_eq!(Pinyin::from_hanzi("广东省").initials, "gds");
+        assert_eq!(Pinyin::from_hanzi("重庆市").full, "chongqingshi");
+        assert_eq!(Pinyin::from_hanzi("厦门市").full, "xiamenshi");
+        assert_eq!(Pinyin::from_hanzi("长沙市").full, "changshashi");
+        // 地名词优先于单字读音
+        assert_eq!(Pinyin::from_hanzi("六合区").full, "luhequ");
+        assert_eq!(Pinyin::from_hanzi("番禺区").full, "panyuqu");
+        assert_eq!(Pinyin::from_hanzi("南山区A座").full, "nanshanqua");
+    }
+
+    #[test]
+    fn test_pinyin_covers_regions() {
+        let table = PinyinTable::load();
+        for region in load_regions() {
+            for ch in region.full_name().chars() {
+                assert!(
+                    table.chars.contains_key(&ch),
+                    "{}: {}",
+                    region.full_name(),
+                    ch
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pinyin_input() {
+        let input = PinyinInput::new("Shenzhen-Nanshan 科技园");
+        assert_eq!(input.compact, "shenzhennanshan");
+        assert_eq!(input.original_end(8), "Shenzhen".len());
+        assert!(input.is_boundary(8));
+        assert!(!input.is_boundary(2));
+        assert!(input.is_boundary(15));
+    }
+}
@@ -0,0 +1,166 @@
+//! 输入预处理：全角转半角、去除不可见字符，并记录与原文位置的对应关系
+
+use std::iter;
+use std::ops::Range;
+
+/// 预处理选项，默认全部开启，见 [`AddressParser::with_preprocess`](crate::AddressParser::with_preprocess)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreprocessOptions {
+    /// 全角字母、数字、标点及全角空格转为半角，如"１２３号" -> "123号"
+    pub full_width: bool,
+    /// 去除零宽字符、控制字符等不可见字符（换行、制表符视为空白保留）
+    pub invisible: bool,
+    /// 跳过各级地名之间的空白和分隔符，如"广东省 深圳市，南山区"
+    pub separators: bool,
+}
+
+impl Default for PreprocessOptions {
+    fn default() -> Self {
+        Self {
+            full_width: true,
+            invisible: true,
+            separators: true,
+        }
+    }
+}
+
+impl PreprocessOptions {
+    /// 关闭全部预处理
+    pub fn none() -> Self {
+        Self {
+            full_width: false,
+            invisible: false,
+            separators: false,
+        }
+    }
+
+    /// 对输入做字符级预处理（不处理分隔符，分隔符在匹配地名时跳过）
+    ///
+    /// ```rust
+    /// use cpca::PreprocessOptions;
+    ///
+    /// let input = "南山区\u{200b}１２３号";
+    /// let text = PreprocessOptions::default().apply(input);
+    /// assert_eq!(text.as_str(), "南山区123号");
+    /// assert_eq!(&input[text.original_range(9..12)], "１２３");
+    /// ```
+    pub fn apply(&self, input: &str) -> Preprocessed {
+        let mut text = String::with_capacity(input.len());
+        let mut origins = Vec::with_capacity(input.len());
+
+        for (pos, original) in input.char_indices() {
+            if self.invisible && is_invisible(original) {
+                continue;
+            }
+            let ch = if self.full_width {
+                to_half_width(original)
+            } else {
+                original
+            };
+            text.push(ch);
+            let origin = pos..pos + original.len_utf8();
+            origins.extend(iter::repeat_n(origin, ch.len_utf8()));
+        }
+
+        Preprocessed {
+            text,
+            origins,
+            input_len: input.len(),
+        }
+    }
+}
+
+/// 预处理后的文本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preprocessed {
+    text: String,
+    /// 每个字节所属字符在原文中的字节范围
+    origins: Vec<Range<usize>>,
+    input_len: usize,
+}
+
+impl Preprocessed {
+    /// 预处理后的文本
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// 把预处理后文本中的字节范围换算为原文中的字节范围
+    ///
+    /// 超出预处理后文本的部分截断到原文末尾。
+    pub fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = self
+            .origins
+            .get(range.start)
+            .map_or(self.input_len, |r| r.start);
+        if range.is_empty() {
+            return start..start;
+        }
+        let end = self
+            .origins
+            .get(range.end - 1)
+            .map_or(self.input_len, |r| r.end);
+        start..end
+    }
+}
+
+/// 各级地名之间可以跳过的分隔符
+pub(crate) fn is_separator(ch: char) -> bool {
+    ch.is_whitespace()
+        || matches!(
+            ch,
+            ',' | '，' | '、' | ';' | '；' | '/' | '|' | '-' | '·' | '_'
+        )
+}
+
+/// 零宽字符、方向控制符及除空白外的控制字符
+fn is_invisible(ch: char) -> bool {
+    (ch.is_control() && !ch.is_whitespace())
+        || matches!(
+            ch,
+            '\u{200b}'..='\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2060}'..='\u{2064}' | '\u{feff}'
+        )
+}
+
+/// 全角 ASCII 字符及全角空格转为半角
+fn to_half_width(ch: char) -> char {
+    match ch {
+        '\u{3000}' => ' ',
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(ch as u32 - 0xfee0).unwrap_or(ch),
+        _ => ch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let input = "\u{feff}广东省\u{3000}深圳市ＡＢＣ\u{200b}大厦";
+        let text = PreprocessOptions::default().apply(input);
+        assert_eq!(text.as_str(), "广东省 深圳市ABC大厦");
+        assert_eq!(&input[text.original_range(0..9)], "广东省");
+        assert_eq!(&input[text.original_range(19..22)], "ＡＢＣ");
+        assert_eq!(text.original_range(22..22), 36..36);
+        assert_eq!(text.original_range(28..28), input.len()..input.len());
+        // 越界的范围截断到原文末尾
+        assert_eq!(text.original_range(19..100), 24..input.len());
+        assert_eq!(text.original_range(50..100), input.len()..input.len());
+
+        let text = PreprocessOptions::none().apply(input);
+        assert_eq!(text.as_str(), input);
+        assert_eq!(text.original_range(3..12), 3..12);
+    }
+
+    #[test]
+    fn test_chars() {
+        assert_eq!(to_half_width('１'), '1');
+        assert_eq!(to_half_width('，'), ',');
+        assert_eq!(to_half_width('中'), '中');
+        assert!(is_invisible('\u{200b}'));
+        assert!(!is_invisible('\n'));
+        assert!(is_separator('、'));
+        assert!(!is_separator('号'));
+    }
+}
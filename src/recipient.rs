@@ -0,0 +1,283 @@
+//! 收件信息拆分：从粘贴的快递单文本中分出姓名、电话、邮编和地址
+
+use crate::detail::is_han;
+use crate::region::ParsedAddress;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// 收件信息，见 [`AddressParser::parse_recipient`](crate::AddressParser::parse_recipient)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recipient {
+    /// 收件人姓名
+    pub name: Option<String>,
+    /// 电话号码，按出现顺序
+    pub phones: Vec<Phone>,
+    /// 邮政编码
+    pub postal_code: Option<String>,
+    /// 地址解析结果，位置相对于原始输入
+    pub address: ParsedAddress,
+}
+
+/// 电话号码
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Phone {
+    /// 规范化的号码：手机号为 11 位数字（去除 +86），带区号的座机为"区号-号码"
+    pub number: String,
+    /// 号码类型
+    pub kind: PhoneKind,
+}
+
+/// 电话号码类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PhoneKind {
+    /// 手机
+    Mobile,
+    /// 座机（含 400、800 号码）
+    Landline,
+}
+
+/// 文本中识别出的号码
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Number {
+    Phone(Phone),
+    PostalCode(String),
+}
+
+/// 字段标签，如"收件人："、"电话："
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Label {
+    Name,
+    Phone,
+    PostalCode,
+    Address,
+}
+
+/// 标签及其类型，较长的标签在前
+const LABELS: &[(&str, Label)] = &[
+    ("收件人姓名", Label::Name),
+    ("收货人姓名", Label::Name),
+    ("收件人", Label::Name),
+    ("收货人", Label::Name),
+    ("联系人", Label::Name),
+    ("姓名", Label::Name),
+    ("联系电话", Label::Phone),
+    ("联系方式", Label::Phone),
+    ("手机号码", Label::Phone),
+    ("手机号", Label::Phone),
+    ("手机", Label::Phone),
+    ("电话", Label::Phone),
+    ("座机", Label::Phone),
+    ("邮政编码", Label::PostalCode),
+    ("邮编", Label::PostalCode),
+    ("收货地址", Label::Address),
+    ("收件地址", Label::Address),
+    ("详细地址", Label::Address),
+    ("所在地区", Label::Address),
+    ("地址", Label::Address),
+];
+
+/// 姓名不会以这些字结尾（地址用字）
+const ADDRESS_SUFFIXES: &[char] = &[
+    '省', '市', '区', '县', '镇', '乡', '村', '路', '街', '道', '巷', '弄', '号', '楼', '栋', '幢',
+    '室', '层', '园', '苑', '府', '庄', '湾', '城', '厦', '店', '场', '站', '口',
+];
+
+/// 找出文本中的电话号码和邮政编码，返回（字节范围, 号码）
+///
+/// 数字组之间可以有单个空格、连字符或括号（如"138 0013 8000"、"(0755)86013388"），
+/// 相邻的数字组优先合成最长的有效号码。
+pub(crate) fn find_numbers(text: &str) -> Vec<(Range<usize>, Number)> {
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if !bytes[pos].is_ascii_digit() {
+            pos += 1;
+            continue;
+        }
+        let groups = digit_groups(bytes, pos);
+        pos = groups.last().map_or(pos + 1, |g| g.end);
+
+        let mut first = 0;
+        while first < groups.len() {
+            let phone = (first + 1..=groups.len()).rev().find_map(|last| {
+                let digits: String = groups[first..last]
+                    .iter()
+                    .map(|g| &text[g.clone()])
+                    .collect();
+                classify_phone(&digits).map(|phone| (last, phone))
+            });
+            let start = groups[first].start;
+            match phone {
+                Some((last, phone)) => {
+                    // 带上前面的 "+" 或 "("
+                    let start = start - usize::from(text[..start].ends_with(['+', '(']));
+                    numbers.push((start..groups[last - 1].end, Number::Phone(phone)));
+                    first = last;
+                }
+                None => {
+                    let group = groups[first].clone();
+                    if group.len() == 6 && bytes[group.start] != b'9' {
+                        let code = text[group.clone()].to_string();
+                        numbers.push((group, Number::PostalCode(code)));
+                    }
+                    first += 1;
+                }
+            }
+        }
+    }
+    numbers
+}
+
+/// 从 `start` 起连续的数字组，组间为单个空格、连字符或右括号
+fn digit_groups(bytes: &[u8], start: usize) -> Vec<Range<usize>> {
+    let digits_from = |pos: usize| {
+        let len = bytes[pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        pos..pos + len
+    };
+    let mut groups = vec![digits_from(start)];
+    loop {
+        let end = groups[groups.len() - 1].end;
+        let gap = match bytes.get(end..end + 2) {
+            Some([b')', b' ' | b'-', ..]) => 2,
+            _ => 1,
+        };
+        let separated = matches!(bytes.get(end), Some(b' ' | b'-' | b')'));
+        match bytes.get(end + gap) {
+            Some(b) if separated && b.is_ascii_digit() => groups.push(digits_from(end + gap)),
+            _ => break,
+        }
+    }
+    groups
+}
+
+/// 按位数和开头判断电话号码
+fn classify_phone(digits: &str) -> Option<Phone> {
+    let digits = match digits
+        .strip_prefix("0086")
+        .or_else(|| digits.strip_prefix("86"))
+    {
+        Some(rest) if rest.len() == 11 && rest.starts_with('1') => rest,
+        _ => digits,
+    };
+    let phone = |number: String, kind| Some(Phone { number, kind });
+    let second = digits.as_bytes().get(1).copied().unwrap_or(b'0');
+    match digits.len() {
+        11 if digits.starts_with('1') && (b'3'..=b'9').contains(&second) => {
+            phone(digits.to_string(), PhoneKind::Mobile)
+        }
+        10 if digits.starts_with("400") || digits.starts_with("800") => {
+            phone(digits.to_string(), PhoneKind::Landline)
+        }
+        10..=12 if digits.starts_with('0') && second != b'0' => {
+            // 北京 010 及 02x 为三位区号，其余为四位
+            let area = if digits.starts_with("010") || second == b'2' {
+                3
+            } else {
+                4
+            };
+            let local = &digits[area..];
+            (7..=8).contains(&local.len()).then(|| Phone {
+                number: format!("{}-{}", &digits[..area], local),
+                kind: PhoneKind::Landline,
+            })
+        }
+        7 | 8 if !digits.starts_with(['0', '1']) => phone(digits.to_string(), PhoneKind::Landline),
+        _ => None,
+    }
+}
+
+/// 去掉开头的字段标签及其后的冒号，返回（标签类型, 剩余部分）
+pub(crate) fn strip_label(segment: &str) -> Option<(Label, &str)> {
+    LABELS.iter().find_map(|&(label, kind)| {
+        let rest = segment.strip_prefix(label)?;
+        let rest = rest.trim_start_matches([':', '：', ' ']);
+        Some((kind, rest))
+    })
+}
+
+/// 是否像姓名：2~4 个汉字（少数民族姓名可含间隔号），且不以地址用字结尾
+pub(crate) fn is_name_like(text: &str) -> bool {
+    let count = text.chars().filter(|&ch| ch != '·').count();
+    let max = if text.contains('·') { 12 } else { 4 };
+    (2..=max).contains(&count)
+        && text.chars().all(|ch| ch == '·' || is_han(ch))
+        && !text.ends_with(ADDRESS_SUFFIXES)
+        && !text.starts_with('·')
+        && !text.ends_with('·')
+}
+
+/// 把文本中的指定范围替换为等长的空格，其余部分的位置不变
+pub(crate) fn mask(text: &str, ranges: &[Range<usize>]) -> String {
+    let mut masked = text.as_bytes().to_vec();
+    for range in ranges {
+        masked[range.clone()].fill(b' ');
+    }
+    // 范围均在字符边界上，替换后仍是合法的 UTF-8
+    String::from_utf8(masked).unwrap_or_else(|_| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phones(text: &str) -> Vec<(String, PhoneKind)> {
+        find_numbers(text)
+            .into_iter()
+            .filter_map(|(_, number)| match number {
+                Number::Phone(phone) => Some((phone.number, phone.kind)),
+                Number::PostalCode(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_numbers() {
+        use PhoneKind::*;
+        assert_eq!(phones("13800138000"), [("13800138000".into(), Mobile)]);
+        assert_eq!(
+            phones("+86 138-0013-8000"),
+            [("13800138000".into(), Mobile)]
+        );
+        assert_eq!(
+            phones("(0755)86013388"),
+            [("0755-86013388".into(), Landline)]
+        );
+        assert_eq!(phones("010-12345678"), [("010-12345678".into(), Landline)]);
+        assert_eq!(phones("400-123-4567"), [("4001234567".into(), Landline)]);
+        assert_eq!(
+            phones("13800138000 0755 86013388"),
+            [
+                ("13800138000".into(), Mobile),
+                ("0755-86013388".into(), Landline)
+            ]
+        );
+        // 门牌号、楼层不是电话
+        assert!(phones("科技园路1号8栋1301室").is_empty());
+
+        let text = "+8613800138000 518000";
+        let numbers = find_numbers(text);
+        assert_eq!(&text[numbers[0].0.clone()], "+8613800138000");
+        assert_eq!(numbers[1], (15..21, Number::PostalCode("518000".into())));
+    }
+
+    #[test]
+    fn test_labels_and_names() {
+        assert_eq!(strip_label("收件人：张三"), Some((Label::Name, "张三")));
+        assert_eq!(strip_label("手机号:"), Some((Label::Phone, "")));
+        assert_eq!(strip_label("张三"), None);
+        assert!(is_name_like("张三"));
+        assert!(is_name_like("买买提·艾力"));
+        assert!(!is_name_like("科技园"));
+        assert!(!is_name_like("张"));
+        assert!(!is_name_like("Tom"));
+        assert_eq!(mask("张三 138", &[0..3, 3..6]), "       138");
+    }
+}
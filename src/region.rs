@@ -1,7 +1,14 @@
 //! 地区数据结构
 
+use crate::detail::DetailParts;
+use crate::fanti::to_fanti;
+use crate::geo::Coordinate;
+use crate::parser::AddressParser;
+use crate::pinyin::Pinyin;
+use crate::preprocess::Preprocessed;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
 
 /// 行政区划记录
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -13,6 +20,91 @@ pub struct Region {
     pub city: String,
     /// 区县（可能为空，如不设区的市）
     pub district: Option<String>,
+    /// 乡镇、街道（仅乡镇级记录）
+    pub town: Option<String>,
+    /// 村、社区（仅村级记录）
+    pub village: Option<String>,
+    /// 省级行政区划代码（GB/T 2260，6 位）
+    pub province_code: Option<String>,
+    /// 市级行政区划代码
+    pub city_code: Option<String>,
+    /// 区县级行政区划代码
+    pub district_code: Option<String>,
+    /// 城市一级的类型（市级代码未收录的地区为空）
+    pub city_kind: Option<RegionKind>,
+}
+
+/// 行政层级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RegionLevel {
+    /// 省级（省、自治区、直辖市、特别行政区）
+    Province,
+    /// 地级（地级市、自治州、地区、盟）
+    City,
+    /// 县级（市辖区、县、县级市、旗）
+    District,
+}
+
+/// 省级以下、区县以上一级（`city` 字段）的行政区划类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RegionKind {
+    /// 直辖市，城市与省份同名
+    Municipality,
+    /// 特别行政区，城市与省份同名
+    SpecialAdministrativeRegion,
+    /// 地级市
+    PrefectureCity,
+    /// 不设区的地级市（直筒子市），下一级直接为乡镇街道，如东莞市
+    NoDistrictCity,
+    /// 自治州
+    AutonomousPrefecture,
+    /// 地区
+    Prefecture,
+    /// 盟
+    League,
+    /// 省直辖县级行政单位，没有地级一级，如湖北省仙桃市、海南省五指山市
+    ProvinceAdministered,
+    /// 新疆生产建设兵团的自治区直辖县级市，如石河子市
+    CorpsCity,
+}
+
+impl RegionKind {
+    /// 中文名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Municipality => "直辖市",
+            Self::SpecialAdministrativeRegion => "特别行政区",
+            Self::PrefectureCity => "地级市",
+            Self::NoDistrictCity => "直筒子市",
+            Self::AutonomousPrefecture => "自治州",
+            Self::Prefecture => "地区",
+            Self::League => "盟",
+            Self::ProvinceAdministered => "省直辖县级行政单位",
+            Self::CorpsCity => "兵团市",
+        }
+    }
+
+    /// 是否为地级行政区（省直辖县级单位、兵团市没有地级一级）
+    pub fn is_prefecture_level(&self) -> bool {
+        matches!(
+            self,
+            Self::PrefectureCity
+                | Self::NoDistrictCity
+                | Self::AutonomousPrefecture
+                | Self::Prefecture
+                | Self::League
+        )
+    }
+
+    /// 下一级是否为乡镇街道而非区县
+    pub fn has_no_districts(&self) -> bool {
+        matches!(
+            self,
+            Self::NoDistrictCity | Self::ProvinceAdministered | Self::CorpsCity
+        )
+    }
 }
 
 impl Region {
@@ -26,15 +118,76 @@ impl Region {
             province: province.into(),
             city: city.into(),
             district,
+            town: None,
+            village: None,
+            province_code: None,
+            city_code: None,
+            district_code: None,
+            city_kind: None,
+        }
+    }
+
+    /// 创建乡镇级记录（`district` 为空表示直属于不设区的市）
+    pub fn with_town(
+        province: impl Into<String>,
+        city: impl Into<String>,
+        district: Option<String>,
+        town: impl Into<String>,
+    ) -> Self {
+        Self {
+            town: Some(town.into()),
+            ..Self::new(province, city, district)
+        }
+    }
+
+    /// 最细一级的行政区划代码（乡镇级及以下暂无代码）
+    pub fn code(&self) -> Option<&str> {
+        if self.town.is_some() || self.village.is_some() {
+            None
+        } else if self.district.is_some() {
+            self.district_code.as_deref()
+        } else {
+            self.city_code.as_deref()
         }
     }
 
     /// 获取完整地址字符串
     pub fn full_name(&self) -> String {
-        match &self.district {
-            Some(d) => format!("{}{}{}", self.province, self.city, d),
-            None => format!("{}{}", self.province, self.city),
+        let mut name = format!("{}{}", self.province, self.city);
+        if let Some(ref d) = self.district {
+            name.push_str(d);
+        }
+        if let Some(ref t) = self.town {
+            name.push_str(t);
+        }
+        if let Some(ref v) = self.village {
+            name.push_str(v);
         }
+        name
+    }
+
+    /// 各级名称的拼音，按省、市、区县、乡镇、村的顺序排列
+    ///
+    /// ```rust
+    /// use cpca::Region;
+    ///
+    /// let region = Region::new("广东省", "深圳市", Some("南山区".to_string()));
+    /// let pinyin = region.pinyin();
+    /// assert_eq!(pinyin[1].full, "shenzhenshi");
+    /// assert_eq!(pinyin[2].initials, "nsq");
+    /// ```
+    pub fn pinyin(&self) -> Vec<Pinyin> {
+        [
+            Some(&self.province),
+            Some(&self.city),
+            self.district.as_ref(),
+            self.town.as_ref(),
+            self.village.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|name| Pinyin::from_hanzi(name))
+        .collect()
     }
 }
 
@@ -48,8 +201,218 @@ pub struct ParsedAddress {
     pub city: Option<String>,
     /// 区县
     pub district: Option<String>,
+    /// 乡镇、街道
+    pub town: Option<String>,
+    /// 村、社区（需通过构建器提供村级数据）
+    pub village: Option<String>,
     /// 剩余详细地址
     pub detail: String,
+    /// 省级行政区划代码
+    pub province_code: Option<String>,
+    /// 市级行政区划代码
+    pub city_code: Option<String>,
+    /// 区县级行政区划代码
+    pub district_code: Option<String>,
+    /// 城市一级的类型，如省直辖县级行政单位没有地级一级
+    pub city_kind: Option<RegionKind>,
+    /// 通过历史名称解析出的地区（已换算为现行名称）
+    pub historical: Vec<HistoricalName>,
+    /// 各级名称的来源
+    pub provenance: Provenance,
+    /// 各级名称及详细地址在原始输入中的位置
+    pub spans: Spans,
+}
+
+/// 地址成分在原始输入（未去除首尾空白）中的位置
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    /// 字节范围，可直接用于切片原始输入
+    pub bytes: Range<usize>,
+    /// 字符范围
+    pub chars: Range<usize>,
+}
+
+impl Span {
+    /// 由字节范围创建，字符范围由 [`Spans::locate`] 补全
+    pub(crate) fn new(bytes: Range<usize>) -> Self {
+        Self { bytes, chars: 0..0 }
+    }
+}
+
+/// 各级名称及详细地址在原始输入中的位置，推断得到或未解析的层级为 `None`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spans {
+    /// 省份
+    pub province: Option<Span>,
+    /// 城市
+    pub city: Option<Span>,
+    /// 区县
+    pub district: Option<Span>,
+    /// 乡镇、街道
+    pub town: Option<Span>,
+    /// 村、社区
+    pub village: Option<Span>,
+    /// 详细地址（英文地址为剩余各段的整体范围）
+    pub detail: Option<Span>,
+}
+
+impl Spans {
+    /// 把预处理后文本中的位置换算为原始输入中的字节、字符范围
+    pub(crate) fn locate(&mut self, input: &Preprocessed, original: &str) {
+        for span in self.iter_mut() {
+            span.bytes = input.original_range(span.bytes.clone());
+        }
+        self.locate_chars(original);
+    }
+
+    /// 各部分位置整体后移 `offset` 字节
+    pub(crate) fn shift(&mut self, offset: usize) {
+        for span in self.iter_mut() {
+            span.bytes = span.bytes.start + offset..span.bytes.end + offset;
+        }
+    }
+
+    /// 覆盖全部已定位部分的最小范围
+    pub(crate) fn extent(&self) -> Option<Span> {
+        let spans = [
+            &self.province,
+            &self.city,
+            &self.district,
+            &self.town,
+            &self.village,
+            &self.detail,
+        ];
+        let mut spans = spans.into_iter().flatten();
+        let first = spans.next()?.clone();
+        Some(spans.fold(first, |a, b| Span {
+            bytes: a.bytes.start.min(b.bytes.start)..a.bytes.end.max(b.bytes.end),
+            chars: a.chars.start.min(b.chars.start)..a.chars.end.max(b.chars.end),
+        }))
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut Span> {
+        [
+            &mut self.province,
+            &mut self.city,
+            &mut self.district,
+            &mut self.town,
+            &mut self.village,
+            &mut self.detail,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// 根据原始输入计算各部分的字符范围
+    fn locate_chars(&mut self, input: &str) {
+        for span in self.iter_mut() {
+            let start = input[..span.bytes.start].chars().count();
+            span.chars = start..start + input[span.bytes.clone()].chars().count();
+        }
+    }
+}
+
+/// 解析结果中各级名称的来源，未解析的层级为 `None`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Provenance {
+    /// 省份
+    pub province: Option<MatchSource>,
+    /// 城市
+    pub city: Option<MatchSource>,
+    /// 区县
+    pub district: Option<MatchSource>,
+    /// 乡镇、街道
+    pub town: Option<MatchSource>,
+    /// 村、社区
+    pub village: Option<MatchSource>,
+}
+
+/// 名称的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MatchSource {
+    /// 地址中写明了全称
+    Explicit,
+    /// 地址中写的是简称或别称（含繁体、拼音、英文）
+    Alias,
+    /// 由下级地区反查得到，如由城市推断省份
+    InferredFromChild,
+    /// 由上级地区得到，如直辖市的城市与省份同名
+    InferredFromParent,
+    /// 由已撤销或更名的历史名称换算得到
+    Historical,
+    /// 地址中的写法有错别字，按编辑距离或同音字纠正得到
+    Fuzzy,
+}
+
+impl MatchSource {
+    /// 该来源的可信程度（0~1）
+    pub fn weight(&self) -> f64 {
+        match self {
+            Self::Explicit | Self::InferredFromParent => 1.0,
+            Self::Alias => 0.9,
+            Self::Historical => 0.85,
+            Self::InferredFromChild => 0.8,
+            Self::Fuzzy => 0.6,
+        }
+    }
+}
+
+/// 历史名称换算记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HistoricalName {
+    /// 地址中的原名称，如"北京市崇文区"
+    pub old_name: String,
+    /// 现行名称，如"北京市东城区"
+    pub new_name: String,
+    /// 调整生效日期（YYYY-MM-DD）
+    pub effective: String,
+}
+
+/// 地址的一种候选解读，见 [`AddressParser::parse_candidates`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Candidate {
+    /// 按该解读得到的解析结果
+    pub address: ParsedAddress,
+    /// 置信度（0~1），同一地址的全部候选合计为 1
+    pub score: f64,
+    /// 列出该候选的原因
+    pub reason: CandidateReason,
+}
+
+/// 从文本中找到的地址，见 [`AddressParser::extract`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtractedAddress {
+    /// 解析结果，各部分的位置相对于整段文本
+    pub address: ParsedAddress,
+    /// 地址整体在文本中的位置
+    pub span: Span,
+}
+
+/// 候选解读的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CandidateReason {
+    /// 地址只有这一种解读
+    Unique,
+    /// 地名（如"朝阳区"）对应多个地区，该候选是其中之一
+    SharedName {
+        /// 地址中的地名
+        name: String,
+        /// 同名地区的数量
+        count: usize,
+    },
+    /// 同名地区中，详细地址里的乡镇街道位于该候选地区
+    TownMatched {
+        /// 乡镇街道名称
+        town: String,
+    },
 }
 
 impl ParsedAddress {
@@ -73,11 +436,121 @@ impl ParsedAddress {
         self.district.is_some()
     }
 
+    /// 是否解析到了乡镇街道
+    pub fn has_town(&self) -> bool {
+        self.town.is_some()
+    }
+
+    /// 是否使用了已撤销或更名的历史名称
+    pub fn is_historical(&self) -> bool {
+        !self.historical.is_empty()
+    }
+
+    /// 把各级地名转换为繁体（详细地址保持原文）
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let result = AddressParser::new().parse("廣東省深圳市南山區科技園");
+    /// assert_eq!(result.district, Some("南山区".to_string()));
+    /// assert_eq!(result.detail, "科技園");
+    /// assert_eq!(result.to_traditional().full_address(), "廣東省深圳市南山區科技園");
+    /// ```
+    pub fn to_traditional(&self) -> ParsedAddress {
+        let convert = |name: &Option<String>| name.as_deref().map(to_fanti);
+        ParsedAddress {
+            province: convert(&self.province),
+            city: convert(&self.city),
+            district: convert(&self.district),
+            town: convert(&self.town),
+            village: convert(&self.village),
+            ..self.clone()
+        }
+    }
+
+    /// 把详细地址拆分为道路、门牌号、小区、楼栋、单元、楼层、房间
+    ///
+    /// ```rust
+    /// use cpca::AddressParser;
+    ///
+    /// let result = AddressParser::new().parse("广东省深圳市南山区深南大道10000号腾讯大厦A座");
+    /// let parts = result.detail_parts();
+    /// assert_eq!(parts.road.as_deref(), Some("深南大道"));
+    /// assert_eq!(parts.compound.as_deref(), Some("腾讯大厦"));
+    /// assert_eq!(parts.building.as_deref(), Some("A"));
+    /// ```
+    pub fn detail_parts(&self) -> DetailParts {
+        DetailParts::parse(&self.detail)
+    }
+
+    /// 是否有经过纠错的地名（来源为 [`MatchSource::Fuzzy`]）
+    pub fn is_fuzzy(&self) -> bool {
+        let p = &self.provenance;
+        [p.province, p.city, p.district, p.town, p.village].contains(&Some(MatchSource::Fuzzy))
+    }
+
+    /// 整体置信度（0~1）
+    ///
+    /// 为各级名称来源可信程度的乘积；城市缺失（如同名区县无法确定城市）、
+    /// 或应有区县而缺失时相应降低，什么都没有解析到时为 0。
+    ///
+    /// ```rust
+    /// let parser = cpca::AddressParser::new();
+    /// assert_eq!(parser.parse("广东省深圳市南山区").confidence(), 1.0);
+    /// assert!(parser.parse("南山区").confidence() < parser.parse("深圳南山").confidence());
+    /// ```
+    pub fn confidence(&self) -> f64 {
+        let p = &self.provenance;
+        let sources = [p.province, p.city, p.district, p.town, p.village];
+        if sources.iter().all(Option::is_none) {
+            return 0.0;
+        }
+        let mut confidence: f64 = sources.iter().flatten().map(MatchSource::weight).product();
+        if self.city.is_none() {
+            confidence *= 0.5;
+        } else if self.district.is_none() && !self.city_kind.is_some_and(|k| k.has_no_districts()) {
+            confidence *= 0.8;
+        }
+        confidence
+    }
+
     /// 是否完整解析（省市区都有）
     pub fn is_complete(&self) -> bool {
         self.province.is_some() && self.city.is_some() && self.district.is_some()
     }
 
+    /// 已解析地区中有坐标的最细一级及其中心点坐标（WGS-84）
+    ///
+    /// 依次查区县、城市、省份，坐标来自 `parser`，见 [`AddressParser::region_centroid`]。
+    pub fn centroid(&self, parser: &AddressParser) -> Option<(RegionLevel, Coordinate)> {
+        let province = self.province.as_deref()?;
+        if let Some(city) = self.city.as_deref() {
+            let district = self.district.as_deref();
+            if let Some(c) = district.and_then(|d| parser.region_centroid(province, city, Some(d)))
+            {
+                return Some((RegionLevel::District, c));
+            }
+            if let Some(c) = parser.region_centroid(province, city, None) {
+                return Some((RegionLevel::City, c));
+            }
+        }
+        parser
+            .region_centroid(province, "", None)
+            .map(|c| (RegionLevel::Province, c))
+    }
+
+    /// 邮政编码，数据来自 `parser`，见 [`AddressParser::postal_code`]
+    pub fn postal_code<'a>(&self, parser: &'a AddressParser) -> Option<&'a str> {
+        let (province, city) = (self.province.as_deref()?, self.city.as_deref()?);
+        parser.postal_code(province, city, self.district.as_deref())
+    }
+
+    /// 电话区号，数据来自 `parser`，见 [`AddressParser::area_code`]
+    pub fn area_code<'a>(&self, parser: &'a AddressParser) -> Option<&'a str> {
+        let (province, city) = (self.province.as_deref()?, self.city.as_deref()?);
+        parser.area_code(province, city, self.district.as_deref())
+    }
+
     /// 获取标准化的完整地址
     pub fn full_address(&self) -> String {
         let mut result = String::new();
@@ -93,6 +566,12 @@ impl ParsedAddress {
         if let Some(ref d) = self.district {
             result.push_str(d);
         }
+        if let Some(ref t) = self.town {
+            result.push_str(t);
+        }
+        if let Some(ref v) = self.village {
+            result.push_str(v);
+        }
         if !self.detail.is_empty() {
             result.push_str(&self.detail);
         }
@@ -111,6 +590,10 @@ mod tests {
 
         let region = Region::new("广东省", "东莞市", None);
         assert_eq!(region.full_name(), "广东省东莞市");
+
+        let region = Region::with_town("广东省", "东莞市", None, "长安镇");
+        assert_eq!(region.full_name(), "广东省东莞市长安镇");
+        assert_eq!(region.code(), None);
     }
 
     #[test]
@@ -120,6 +603,7 @@ mod tests {
             city: Some("深圳市".to_string()),
             district: Some("南山区".to_string()),
             detail: "科技园".to_string(),
+            ..Default::default()
         };
         assert!(addr.is_complete());
         assert_eq!(addr.full_address(), "广东省深圳市南山区科技园");
@@ -132,6 +616,7 @@ mod tests {
             city: Some("北京市".to_string()),
             district: Some("朝阳区".to_string()),
             detail: "望京".to_string(),
+            ..Default::default()
         };
         // 直辖市不重复显示
         assert_eq!(addr.full_address(), "北京市朝阳区望京");
@@ -0,0 +1,280 @@
+//! 行政区划树：按省、市、区县逐级浏览，可用于级联选择等场景
+
+use crate::data::RegionIndex;
+use crate::pinyin::Pinyin;
+use crate::region::RegionLevel;
+use std::fmt;
+
+/// 行政区划树中的一个节点，见 [`AddressParser::province_nodes`](crate::AddressParser::province_nodes)
+///
+/// 节点借用解析器的索引，可随意复制。同级节点按行政区划代码排序，
+/// 没有代码的排在有代码的之后，彼此按拼音（相同时按名称）排序，结果与构建顺序无关。
+/// 内置数据中没有代码的有：台湾省的县市区、港澳的城市及其下属地区、"国外"，
+/// 以及 2022 年以后新设、代码表尚未收录的区县，如米林市、错那市排在同级区县之后。
+/// 直辖市、特别行政区的城市与省同名，如北京市 → 北京市 → 朝阳区。
+///
+/// ```rust
+/// use cpca::{AddressParser, RegionLevel};
+///
+/// let parser = AddressParser::new();
+/// let guangdong = parser.region_node("广东省", None, None).unwrap();
+/// let cities = guangdong.children();
+/// assert_eq!(cities[0].name(), "广州市");
+/// assert_eq!(cities[0].code(), Some("440100"));
+///
+/// let nanshan = parser.region_node("广东省", Some("深圳市"), Some("南山区")).unwrap();
+/// assert_eq!(nanshan.level(), RegionLevel::District);
+/// assert_eq!(nanshan.parent().unwrap().name(), "深圳市");
+/// ```
+#[derive(Clone, Copy)]
+pub struct RegionNode<'a> {
+    index: &'a RegionIndex,
+    province: &'a str,
+    city: Option<&'a str>,
+    district: Option<&'a str>,
+}
+
+impl<'a> RegionNode<'a> {
+    /// 全部省级节点
+    pub(crate) fn provinces(index: &'a RegionIndex) -> Vec<RegionNode<'a>> {
+        let nodes = index.provinces.iter().map(|province| RegionNode {
+            index,
+            province,
+            city: None,
+            district: None,
+        });
+        sorted(nodes)
+    }
+
+    /// 按全称查找节点，城市须属于该省，区县须属于该市
+    pub(crate) fn find(
+        index: &'a RegionIndex,
+        province: &str,
+        city: Option<&str>,
+        district: Option<&str>,
+    ) -> Option<RegionNode<'a>> {
+        let province = index.provinces.get(province)?;
+        let city = match city {
+            Some(city) => Some(index.province_cities.get(province)?.get(city)?),
+            None => None,
+        };
+        let district = match (city, district) {
+            (Some(city), Some(district)) => Some(index.city_districts.get(city)?.get(district)?),
+            (None, Some(_)) => return None,
+            (_, None) => None,
+        };
+        Some(RegionNode {
+            index,
+            province,
+            city: city.map(String::as_str),
+            district: district.map(String::as_str),
+        })
+    }
+
+    /// 层级
+    pub fn level(&self) -> RegionLevel {
+        match (self.city, self.district) {
+            (_, Some(_)) => RegionLevel::District,
+            (Some(_), None) => RegionLevel::City,
+            (None, None) => RegionLevel::Province,
+        }
+    }
+
+    /// 名称（全称）
+    pub fn name(&self) -> &'a str {
+        self.district.or(self.city).unwrap_or(self.province)
+    }
+
+    /// 行政区划代码，未收录时为 `None`
+    pub fn code(&self) -> Option<&'a str> {
+        let code = match (self.city, self.district) {
+            (Some(city), Some(district)) => self
+                .index
+                .district_codes
+                .get(&(city.to_string(), district.to_string())),
+            (Some(city), None) => self.index.city_codes.get(city),
+            (None, _) => self.index.province_codes.get(self.province),
+        };
+        code.map(String::as_str)
+    }
+
+    /// 所属省份
+    pub fn province(&self) -> &'a str {
+        self.province
+    }
+
+    /// 所属城市，省级节点为 `None`
+    pub fn city(&self) -> Option<&'a str> {
+        self.city
+    }
+
+    /// 上级节点，省级节点为 `None`
+    pub fn parent(&self) -> Option<RegionNode<'a>> {
+        let parent = match (self.city, self.district) {
+            (_, Some(_)) => RegionNode {
+                district: None,
+                ..*self
+            },
+            (Some(_), None) => RegionNode {
+                city: None,
+                ..*self
+            },
+            (None, None) => return None,
+        };
+        Some(parent)
+    }
+
+    /// 直接下级节点，区县节点没有下级（乡镇街道不在树中）
+    pub fn children(&self) -> Vec<RegionNode<'a>> {
+        let index = self.index;
+        match (self.city, self.district) {
+            (None, _) => sorted(
+                index
+                    .province_cities
+                    .get(self.province)
+                    .into_iter()
+                    .flatten()
+                    .map(|city| RegionNode {
+                        city: Some(city),
+                        ..*self
+                    }),
+            ),
+            (Some(city), None) => sorted(index.city_districts.get(city).into_iter().flatten().map(
+                |district| RegionNode {
+                    district: Some(district),
+                    ..*self
+                },
+            )),
+            (Some(_), Some(_)) => Vec::new(),
+        }
+    }
+
+    /// 全部上级节点，由近及远（区县节点为城市、省份）
+    pub fn ancestors(&self) -> Vec<RegionNode<'a>> {
+        let mut ancestors = Vec::new();
+        let mut node = *self;
+        while let Some(parent) = node.parent() {
+            ancestors.push(parent);
+            node = parent;
+        }
+        ancestors
+    }
+
+    /// 全部下级节点，深度优先，每个节点紧跟其下级
+    pub fn descendants(&self) -> Vec<RegionNode<'a>> {
+        let mut descendants = Vec::new();
+        for child in self.children() {
+            descendants.push(child);
+            descendants.extend(child.descendants());
+        }
+        descendants
+    }
+
+    /// 拼接完整名称，如"广东省深圳市南山区"
+    pub fn full_name(&self) -> String {
+        [Some(self.province), self.city, self.district]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    fn key(&self) -> (&'a str, Option<&'a str>, Option<&'a str>) {
+        (self.province, self.city, self.district)
+    }
+}
+
+/// 同级节点排序：有代码的按代码，其余排在之后，按拼音、名称
+fn sorted<'a>(nodes: impl Iterator<Item = RegionNode<'a>>) -> Vec<RegionNode<'a>> {
+    let mut nodes: Vec<_> = nodes.collect();
+    nodes.sort_by_cached_key(|node| {
+        let code = node.code();
+        (
+            code.is_none(),
+            code,
+            Pinyin::from_hanzi(node.name()).full,
+            node.name(),
+        )
+    });
+    nodes
+}
+
+impl PartialEq for RegionNode<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for RegionNode<'_> {}
+
+impl fmt::Debug for RegionNode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegionNode")
+            .field("level", &self.level())
+            .field("name", &self.full_name())
+            .field("code", &self.code())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::AddressParser;
+    use crate::region::RegionLevel;
+
+    #[test]
+    fn test_navigation() {
+        let parser = AddressParser::new();
+        let provinces = parser.province_nodes();
+        assert_eq!(provinces.len(), parser.provinces().len());
+        assert_eq!(provinces[0].name(), "北京市");
+        assert_eq!(provinces[0].level(), RegionLevel::Province);
+        assert!(provinces[0].parent().is_none());
+
+        let beijing = provinces[0].children();
+        assert_eq!(beijing.len(), 1);
+        assert_eq!(beijing[0].name(), "北京市");
+        assert_eq!(beijing[0].children()[0].name(), "东城区");
+
+        let shenzhen = parser.region_node("广东省", Some("深圳市"), None).unwrap();
+        let names: Vec<_> = shenzhen.children().iter().map(|n| n.name()).collect();
+        assert_eq!(&names[..3], ["罗湖区", "福田区", "南山区"]);
+
+        let nanshan = shenzhen.children()[2];
+        assert_eq!(nanshan.full_name(), "广东省深圳市南山区");
+        assert_eq!(nanshan.code(), Some("440305"));
+        assert!(nanshan.children().is_empty());
+        let ancestors: Vec<_> = nanshan.ancestors().iter().map(|n| n.name()).collect();
+        assert_eq!(ancestors, ["深圳市", "广东省"]);
+
+        let guangdong = nanshan.ancestors()[1];
+        let descendants = guangdong.descendants();
+        assert_eq!(descendants[0].name(), "广州市");
+        assert_eq!(descendants[1].parent().unwrap().name(), "广州市");
+        assert!(descendants.contains(&nanshan));
+
+        // 没有代码的排在同级之后，按拼音排序
+        let nyingchi = parser
+            .region_node("西藏自治区", Some("林芝市"), None)
+            .unwrap();
+        let last = *nyingchi.children().last().unwrap();
+        assert_eq!((last.name(), last.code()), ("米林市", None));
+        let hongkong = parser
+            .region_node("香港特别行政区", Some("香港特别行政区"), None)
+            .unwrap();
+        let names: Vec<_> = hongkong.children().iter().map(|n| n.name()).collect();
+        assert_eq!(&names[..3], ["北区", "大埔区", "东区"]);
+        assert_eq!(provinces.last().unwrap().name(), "国外");
+
+        // 与索引的构建顺序无关
+        let other = AddressParser::new();
+        assert!(other
+            .province_nodes()
+            .iter()
+            .map(|n| n.full_name())
+            .eq(provinces.iter().map(|n| n.full_name())));
+
+        assert!(parser.region_node("广东省", Some("杭州市"), None).is_none());
+        assert!(parser.region_node("广东省", None, Some("南山区")).is_none());
+        assert!(parser.region_node("广东", None, None).is_none());
+    }
+}
@@ -23,6 +23,22 @@ impl<T> Default for TrieNode<T> {
     }
 }
 
+/// 模糊查找时考察的文本字数，长于任何地名
+const FUZZY_WINDOW: usize = 32;
+
+/// 模糊匹配结果
+#[derive(Debug, PartialEq)]
+pub struct FuzzyMatch<'a, T> {
+    /// 关联数据
+    pub value: &'a T,
+    /// 匹配的文本长度（字节）
+    pub len: usize,
+    /// 编辑距离
+    pub distance: usize,
+    /// 词的字数
+    pub word_len: usize,
+}
+
 /// 前缀树，用于快速匹配地名
 #[derive(Debug)]
 pub struct Trie<T> {
@@ -56,7 +72,6 @@ impl<T: Clone> Trie<T> {
     }
 
     /// 查找精确匹配
-    #[allow(dead_code)]
     pub fn get(&self, word: &str) -> Option<&T> {
         let mut node = &self.root;
         for ch in word.chars() {
@@ -104,10 +119,79 @@ impl<T: Clone> Trie<T> {
         last_match
     }
 
+    /// 从文本开头查找所有匹配（由短到长）
+    ///
+    /// 返回 Vec<(匹配的词, 关联数据, 匹配长度)>
+    pub fn find_prefixes<'a>(&self, text: &'a str) -> Vec<(&'a str, &T, usize)> {
+        let mut node = &self.root;
+        let mut matches = Vec::new();
+        let mut current_len = 0;
+
+        for ch in text.chars() {
+            match node.children.get(&ch) {
+                Some(n) => {
+                    node = n;
+                    current_len += ch.len_utf8();
+                    if let Some(ref value) = node.value {
+                        matches.push((&text[..current_len], value, current_len));
+                    }
+                }
+                None => break,
+            }
+        }
+
+        matches
+    }
+
+    /// 从文本开头模糊查找，允许编辑距离（替换、插入、删除）不超过 `max_distance`
+    ///
+    /// `equivalent` 判断两个不同的字是否视为相同（如同音字），相同的字不计入距离。
+    /// 每个词取距离最小的前缀（距离相同时取较长的），返回 Vec<模糊匹配>。
+    pub fn find_fuzzy_prefixes(
+        &self,
+        text: &str,
+        max_distance: usize,
+        equivalent: impl Fn(char, char) -> bool,
+    ) -> Vec<FuzzyMatch<'_, T>> {
+        let mut chars: Vec<(char, usize)> = Vec::new();
+        for (pos, ch) in text.char_indices().take(FUZZY_WINDOW) {
+            chars.push((ch, pos + ch.len_utf8()));
+        }
+        // row[j]：当前词前缀与文本前 j 个字的编辑距离
+        let row: Vec<usize> = (0..=chars.len()).collect();
+        let mut matches = Vec::new();
+        let mut stack = vec![(&self.root, row, 0)];
+
+        while let Some((node, row, depth)) = stack.pop() {
+            for (&ch, child) in &node.children {
+                let mut next = vec![row[0] + 1];
+                for (j, &(c, _)) in chars.iter().enumerate() {
+                    let cost = usize::from(c != ch && !equivalent(c, ch));
+                    next.push((row[j + 1] + 1).min(next[j] + 1).min(row[j] + cost));
+                }
+                if let Some(ref value) = child.value {
+                    let best = (1..next.len()).min_by_key(|&j| (next[j], usize::MAX - j));
+                    if let Some(j) = best.filter(|&j| next[j] <= max_distance) {
+                        matches.push(FuzzyMatch {
+                            value,
+                            len: chars[j - 1].1,
+                            distance: next[j],
+                            word_len: depth + 1,
+                        });
+                    }
+                }
+                if next.iter().min().is_some_and(|&d| d <= max_distance) {
+                    stack.push((child, next, depth + 1));
+                }
+            }
+        }
+
+        matches
+    }
+
     /// 查找文本中所有匹配的词
     ///
     /// 返回 Vec<(起始位置, 匹配词, 关联数据)>
-    #[allow(dead_code)]
     pub fn find_all<'a>(&self, text: &'a str) -> Vec<(usize, &'a str, &T)> {
         let mut results = Vec::new();
         let chars: Vec<char> = text.chars().collect();
@@ -159,6 +243,47 @@ mod tests {
         assert_eq!(len, "广东省".len());
     }
 
+    #[test]
+    fn test_find_prefixes() {
+        let mut trie = Trie::new();
+        trie.insert("sh", 1);
+        trie.insert("shenzhen", 2);
+
+        let results = trie.find_prefixes("shenzhennanshan");
+        assert_eq!(results.len(), 2);
+        assert_eq!((results[0].0, *results[0].1), ("sh", 1));
+        assert_eq!((results[1].0, *results[1].1), ("shenzhen", 2));
+        assert!(trie.find_prefixes("beijing").is_empty());
+    }
+
+    #[test]
+    fn test_find_fuzzy_prefixes() {
+        let mut trie = Trie::new();
+        trie.insert("深圳市", 1);
+        trie.insert("杭州市", 2);
+        trie.insert("南山区", 3);
+
+        let exact = |a: char, b: char| a == b;
+        let results = trie.find_fuzzy_prefixes("深川市南山区", 1, exact);
+        assert_eq!(results.len(), 1);
+        assert_eq!(*results[0].value, 1);
+        assert_eq!(results[0].len, "深川市".len());
+        assert_eq!(results[0].distance, 1);
+        assert_eq!(results[0].word_len, 3);
+
+        // 漏字、多字
+        let results = trie.find_fuzzy_prefixes("深市南山区", 1, exact);
+        assert_eq!((*results[0].value, results[0].len), (1, "深市".len()));
+        let results = trie.find_fuzzy_prefixes("深圳圳市", 1, exact);
+        assert_eq!((*results[0].value, results[0].len), (1, "深圳圳市".len()));
+        assert!(trie.find_fuzzy_prefixes("北京市", 1, exact).is_empty());
+
+        // 视为相同的字不计入距离
+        let results =
+            trie.find_fuzzy_prefixes("杭洲市", 0, |a, b| a == b || (a, b) == ('洲', '州'));
+        assert_eq!((*results[0].value, results[0].distance), (2, 0));
+    }
+
     #[test]
     fn test_find_all() {
         let mut trie = Trie::new();
@@ -0,0 +1,387 @@
+//! 按年份的行政区划数据集及年份间差异
+//!
+//! 历史年份取自 GB/T 2260 历年行政区划代码表（`data/vintage.csv`）：2002—2016 年为各年
+//! 最后一版代码表，2022 年为民政部发布的县级以上行政区划代码；[`CURRENT_VINTAGE`] 为内置的
+//! 现行数据。其余年份没有对应的代码表，返回 [`ParseError::UnsupportedVintage`]。
+//! 港澳台不在代码表的范围内，各年份均沿用现行数据。
+
+use crate::data::{classify_city, load_history, load_regions, Redirect};
+use crate::error::ParseError;
+use crate::region::Region;
+use std::collections::HashMap;
+
+const VINTAGE_DATA: &str = include_str!("../data/vintage.csv");
+
+/// 内置行政区划数据的年份
+pub const CURRENT_VINTAGE: u16 = 2025;
+
+/// 支持的年份（由早到晚）
+pub const VINTAGES: [u16; 17] = [
+    2002,
+    2003,
+    2004,
+    2005,
+    2006,
+    2007,
+    2008,
+    2009,
+    2010,
+    2011,
+    2012,
+    2013,
+    2014,
+    2015,
+    2016,
+    2022,
+    CURRENT_VINTAGE,
+];
+
+/// 不在代码表范围内、沿用现行数据的省级行政区
+const UNCODED_PROVINCES: [&str; 3] = ["台湾省", "香港特别行政区", "澳门特别行政区"];
+
+/// 两个年份之间的地区变化
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegionChange {
+    /// 新设地区
+    Added(Region),
+    /// 撤销地区（含并入其他地区）
+    Removed(Region),
+    /// 更名（含所属城市更名的区县）
+    Renamed {
+        /// 原地区
+        from: Region,
+        /// 现地区
+        to: Region,
+    },
+    /// 改隶，名称不变而所属城市改变
+    Reparented {
+        /// 原地区
+        from: Region,
+        /// 现地区
+        to: Region,
+    },
+}
+
+/// 生效年份在 `(from, to]` 之间的调整记录，按生效日期由早到晚排列
+fn redirects_between(from: u16, to: u16) -> Vec<Redirect> {
+    let mut redirects: Vec<Redirect> = load_history()
+        .into_iter()
+        .filter(|r| (from.saturating_add(1)..=to).contains(&r.effective_year()))
+        .collect();
+    redirects.sort_by(|a, b| a.effective.cmp(&b.effective));
+    redirects
+}
+
+/// 指定年份的省市区数据，带行政区划代码及城市类型
+pub(crate) fn vintage_regions(year: u16) -> Result<Vec<Region>, ParseError> {
+    if year == CURRENT_VINTAGE {
+        return Ok(load_regions());
+    }
+    if !VINTAGES.contains(&year) {
+        return Err(ParseError::UnsupportedVintage(year));
+    }
+
+    // 各行为 adcode,sheng,shi,qu,since,until，since..=until 为收录该地区的年份
+    let rows: Vec<Vec<&str>> = VINTAGE_DATA
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').map(str::trim).collect::<Vec<_>>())
+        .filter(|parts| {
+            let year_at = |i: usize| parts.get(i).and_then(|y| y.parse::<u16>().ok());
+            parts.len() >= 6
+                && year_at(4).is_some_and(|since| since <= year)
+                && year_at(5).is_some_and(|until| year <= until)
+        })
+        .collect();
+    let city_codes: HashMap<(&str, &str), &str> = rows
+        .iter()
+        .filter(|parts| parts[3].is_empty())
+        .map(|parts| ((parts[1], parts[2]), parts[0]))
+        .collect();
+
+    let mut regions: Vec<Region> = rows
+        .iter()
+        .map(|parts| {
+            let (code, province, city) = (parts[0], parts[1], parts[2]);
+            let district = Some(parts[3].to_string()).filter(|d| !d.is_empty());
+            let mut region = Region::new(province, city, district);
+            region.province_code = code.get(..2).map(|prefix| format!("{}0000", prefix));
+            region.city_code = city_codes.get(&(province, city)).map(|c| c.to_string());
+            region.district_code = region.district.as_ref().map(|_| code.to_string());
+            region.city_kind = classify_city(province, city, region.city_code.as_deref());
+            region
+        })
+        .collect();
+    regions.extend(
+        load_regions()
+            .into_iter()
+            .filter(|r| UNCODED_PROVINCES.contains(&r.province.as_str())),
+    );
+
+    Ok(regions)
+}
+
+type RegionKey = (String, String, Option<String>);
+
+fn key_of(region: &Region) -> RegionKey {
+    (
+        region.province.clone(),
+        region.city.clone(),
+        region.district.clone(),
+    )
+}
+
+/// 地区自身的行政区划代码（城市一级为市级代码）
+fn code_of(region: &Region) -> Option<&String> {
+    match region.district {
+        Some(_) => region.district_code.as_ref(),
+        None => region.city_code.as_ref(),
+    }
+}
+
+/// 列出两个年份之间的地区变化（`from` 可以晚于 `to`，此时按逆向列出）
+///
+/// 行政区划代码不变而名称改变的为更名（所属城市更名的区县同样算作更名），
+/// 代码改变的按 `data/history.csv` 中的调整记录配对为更名或改隶，其余为撤销和新设。
+/// 依次列出按代码配对的、按调整记录配对的（按生效先后），其后为撤销和新设（按名称排序）。
+///
+/// ```rust
+/// use cpca::RegionChange;
+///
+/// let changes = cpca::diff(2009, 2025).unwrap();
+/// assert!(changes.iter().any(|c| matches!(
+///     c,
+///     RegionChange::Removed(r) if r.district.as_deref() == Some("崇文区")
+/// )));
+/// ```
+pub fn diff(from: u16, to: u16) -> Result<Vec<RegionChange>, ParseError> {
+    let mut removed: HashMap<RegionKey, Region> = HashMap::new();
+    let mut added: HashMap<RegionKey, Region> = HashMap::new();
+    {
+        let old: HashMap<RegionKey, Region> = vintage_regions(from)?
+            .into_iter()
+            .map(|r| (key_of(&r), r))
+            .collect();
+        let new: HashMap<RegionKey, Region> = vintage_regions(to)?
+            .into_iter()
+            .map(|r| (key_of(&r), r))
+            .collect();
+        for (key, region) in &old {
+            if !new.contains_key(key) {
+                removed.insert(key.clone(), region.clone());
+            }
+        }
+        for (key, region) in new {
+            if !old.contains_key(&key) {
+                added.insert(key, region);
+            }
+        }
+    }
+
+    // 按代码配对：(变化前, 变化后, 是否改隶)
+    let mut by_code: Vec<(RegionKey, RegionKey, bool)> = Vec::new();
+    for (key, region) in &removed {
+        let Some(code) = code_of(region) else {
+            continue;
+        };
+        if let Some((new_key, _)) = added.iter().find(|(_, r)| code_of(r) == Some(code)) {
+            by_code.push((key.clone(), new_key.clone(), false));
+        }
+    }
+    by_code.sort();
+
+    // 按调整记录配对，记录中的地区按时间先后为 (原地区, 现地区)
+    let mut by_history: Vec<(RegionKey, RegionKey, bool)> = Vec::new();
+    for r in redirects_between(from.min(to), from.max(to)) {
+        if r.merge {
+            continue;
+        }
+        let pair = |old: RegionKey, new: RegionKey, reparented: bool| {
+            if from <= to {
+                (old, new, reparented)
+            } else {
+                (new, old, reparented)
+            }
+        };
+        match r.district {
+            Some(ref district) => by_history.push(pair(
+                (r.province.clone(), r.city.clone(), Some(district.clone())),
+                (
+                    r.province.clone(),
+                    r.new_city.clone(),
+                    r.new_district.clone(),
+                ),
+                r.new_district.as_ref() == Some(district),
+            )),
+            None => {
+                by_history.push(pair(
+                    (r.province.clone(), r.city.clone(), None),
+                    (r.province.clone(), r.new_city.clone(), None),
+                    false,
+                ));
+                // 城市更名时，其下属区县一并更名
+                let mut children: Vec<&Option<String>> = removed
+                    .keys()
+                    .chain(added.keys())
+                    .filter(|k| k.2.is_some() && (k.1 == r.city || k.1 == r.new_city))
+                    .map(|k| &k.2)
+                    .collect();
+                children.sort();
+                children.dedup();
+                for district in children {
+                    by_history.push(pair(
+                        (r.province.clone(), r.city.clone(), district.clone()),
+                        (r.province.clone(), r.new_city.clone(), district.clone()),
+                        false,
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (a, b, reparented) in by_code.into_iter().chain(by_history) {
+        if !(removed.contains_key(&a) && added.contains_key(&b)) {
+            continue;
+        }
+        let (Some(from_region), Some(to_region)) = (removed.remove(&a), added.remove(&b)) else {
+            continue;
+        };
+        changes.push(if reparented {
+            RegionChange::Reparented {
+                from: from_region,
+                to: to_region,
+            }
+        } else {
+            RegionChange::Renamed {
+                from: from_region,
+                to: to_region,
+            }
+        });
+    }
+
+    let mut removed: Vec<Region> = removed.into_values().collect();
+    removed.sort_by_key(Region::full_name);
+    let mut added: Vec<Region> = added.into_values().collect();
+    added.sort_by_key(Region::full_name);
+    changes.extend(removed.into_iter().map(RegionChange::Removed));
+    changes.extend(added.into_iter().map(RegionChange::Added));
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region::RegionKind;
+
+    fn has_district(regions: &[Region], city: &str, district: &str) -> bool {
+        regions
+            .iter()
+            .any(|r| r.city == city && r.district.as_deref() == Some(district))
+    }
+
+    #[test]
+    fn test_vintage_regions() {
+        let regions = vintage_regions(2009).unwrap();
+        assert!(has_district(&regions, "北京市", "崇文区"));
+        assert!(has_district(&regions, "北京市", "东城区"));
+        assert!(has_district(&regions, "襄樊市", "襄阳区"));
+        assert!(has_district(&regions, "襄樊市", "樊城区"));
+        assert!(!regions.iter().any(|r| r.city == "襄阳市"));
+        assert!(has_district(&regions, "巢湖市", "居巢区"));
+        assert!(!has_district(&regions, "合肥市", "巢湖市"));
+
+        let regions = vintage_regions(2012).unwrap();
+        assert!(!has_district(&regions, "北京市", "崇文区"));
+        assert!(has_district(&regions, "上海市", "闸北区"));
+
+        assert_eq!(vintage_regions(CURRENT_VINTAGE).unwrap(), load_regions());
+        for year in [1990, 2001, 2017, 2019, 2021, 2024, CURRENT_VINTAGE + 1] {
+            assert_eq!(
+                vintage_regions(year),
+                Err(ParseError::UnsupportedVintage(year))
+            );
+        }
+    }
+
+    #[test]
+    fn test_vintage_codes() {
+        let regions = vintage_regions(2009).unwrap();
+        let find = |city: &str, district: Option<&str>| {
+            regions
+                .iter()
+                .find(|r| r.city == city && r.district.as_deref() == district)
+                .unwrap()
+        };
+
+        let chongwen = find("北京市", Some("崇文区"));
+        assert_eq!(chongwen.province_code.as_deref(), Some("110000"));
+        assert_eq!(chongwen.city_code.as_deref(), Some("110100"));
+        assert_eq!(chongwen.district_code.as_deref(), Some("110103"));
+        assert_eq!(chongwen.city_kind, Some(RegionKind::Municipality));
+
+        let chaohu = find("巢湖市", None);
+        assert_eq!(chaohu.city_code.as_deref(), Some("341400"));
+        assert_eq!(chaohu.city_kind, Some(RegionKind::PrefectureCity));
+        assert_eq!(
+            find("铜仁地区", None).city_kind,
+            Some(RegionKind::Prefecture)
+        );
+        assert_eq!(
+            find("仙桃市", None).city_kind,
+            Some(RegionKind::ProvinceAdministered)
+        );
+
+        // 每个年份的区县都有代码，港澳台沿用现行数据
+        for year in VINTAGES {
+            let regions = vintage_regions(year).unwrap();
+            assert!(regions.len() > 3000, "{}", year);
+            assert!(regions
+                .iter()
+                .filter(|r| !UNCODED_PROVINCES.contains(&r.province.as_str()))
+                .all(|r| r.city_code.is_some() || year == CURRENT_VINTAGE));
+            assert!(regions.iter().any(|r| r.province == "香港特别行政区"));
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let changes = diff(2009, 2025).unwrap();
+
+        assert!(changes.iter().any(|c| matches!(
+            c,
+            RegionChange::Renamed { from, to } if from.city == "襄樊市" && from.district.is_none() && to.city == "襄阳市"
+        )));
+        assert!(changes.iter().any(|c| matches!(
+            c,
+            RegionChange::Renamed { from, to } if from.district.as_deref() == Some("襄阳区") && to.district.as_deref() == Some("襄州区")
+        )));
+        assert!(changes.iter().any(|c| matches!(
+            c,
+            RegionChange::Reparented { from, to } if from.city == "巢湖市" && to.city == "马鞍山市" && to.district.as_deref() == Some("和县")
+        )));
+        assert!(changes.iter().any(
+            |c| matches!(c, RegionChange::Removed(r) if r.city == "巢湖市" && r.district.is_none())
+        ));
+        // 代码不变的更名
+        assert!(changes.iter().any(|c| matches!(
+            c,
+            RegionChange::Renamed { from, to } if from.district.as_deref() == Some("桥东区") && to.district.as_deref() == Some("襄都区")
+        )));
+        // 2009 年之后新设的地区
+        assert!(changes.iter().any(
+            |c| matches!(c, RegionChange::Added(r) if r.city == "三沙市" && r.district.is_none())
+        ));
+
+        // 逆向
+        let reversed = diff(2025, 2009).unwrap();
+        assert_eq!(reversed.len(), changes.len());
+        assert!(reversed.iter().any(|c| matches!(
+            c,
+            RegionChange::Added(r) if r.district.as_deref() == Some("崇文区")
+        )));
+
+        assert!(diff(2012, 2012).unwrap().is_empty());
+    }
+}
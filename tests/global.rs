@@ -0,0 +1,35 @@
+//! 全局解析器的替换会影响同一进程中的其他测试，单独放在一个测试程序中
+
+use cpca::{AddressParser, Aliases, RegionLevel};
+
+#[test]
+fn test_reload_global() {
+    let before = AddressParser::current();
+    let mut aliases = Aliases::new();
+    aliases.add(RegionLevel::City, "帝都", "北京市");
+    cpca::reload_global(AddressParser::builder().aliases(aliases)).unwrap();
+    assert_eq!(
+        cpca::parse("帝都海淀区").district,
+        Some("海淀区".to_string())
+    );
+    assert_eq!(
+        AddressParser::current().parse("帝都").city,
+        Some("北京市".to_string())
+    );
+    // 已取得的旧实例不受影响
+    assert_eq!(before.parse("帝都海淀区").city, None);
+    #[allow(deprecated)]
+    let initial = AddressParser::global();
+    assert_eq!(initial.parse("帝都").city, None);
+
+    // 构建失败时保持原实例
+    let mut unknown = Aliases::new();
+    unknown.add(RegionLevel::City, "帝都", "帝都市");
+    assert!(cpca::reload_global(AddressParser::builder().aliases(unknown)).is_err());
+    assert_eq!(cpca::parse("帝都").city, Some("北京市".to_string()));
+
+    // 恢复为默认配置
+    cpca::reload_global(AddressParser::builder()).unwrap();
+    assert_eq!(cpca::parse("帝都").city, None);
+    assert_eq!(cpca::parse("北京市朝阳区"), before.parse("北京市朝阳区"));
+}